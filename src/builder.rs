@@ -0,0 +1,62 @@
+use alloc::vec::Vec;
+
+use crate::MagicString;
+
+/// Collects string slices so a [`MagicString`] can be made from them, for when the
+/// slices aren't known up front.
+/// ```
+/// use magicstring::{MagicStringBuilder, MagicStringTrait};
+/// let mut builder = MagicStringBuilder::new();
+/// for word in "a b c".split(' ') {
+///     builder.push_str(word);
+/// }
+/// assert_eq!(builder.finish().to_string(), "abc".to_string());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MagicStringBuilder<'a> {
+    slices: Vec<&'a str>,
+}
+
+impl<'a> MagicStringBuilder<'a> {
+    /// Create an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a slice to the end of the string.
+    pub fn push_str(&mut self, s: &'a str) {
+        self.slices.push(s);
+    }
+
+    /// View the slices pushed so far as a [`MagicString`], which borrows the builder.
+    pub fn finish(&self) -> MagicString<'_> {
+        MagicString::new(&self.slices)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MagicStringTrait;
+
+    #[test]
+    fn build() {
+        let text = String::from("one two  three");
+        let mut builder = MagicStringBuilder::new();
+        assert!(builder.finish().is_empty());
+
+        for word in text.split(' ') {
+            builder.push_str(word);
+            builder.push_str("-");
+        }
+        let s = ["one", "-", "two", "-", "", "-", "three", "-"];
+        let expected = MagicString::new(&s);
+        assert_eq!(builder.finish(), expected);
+        assert!(builder.finish().iter().eq(expected.iter()));
+
+        // Pushing more after finishing extends the string
+        builder.push_str("!");
+        assert_eq!(builder.finish().to_string(), "one-two--three-!");
+        assert_eq!(builder.finish().get(4..7).to_string(), "two");
+    }
+}
@@ -0,0 +1,51 @@
+// Searching the bytes of a slice for a single byte, using the `memchr` crate
+// when its feature is enabled
+
+#[cfg(feature = "memchr")]
+pub(crate) use memchr::{memchr as find, memrchr as rfind};
+
+#[cfg(not(feature = "memchr"))]
+pub(crate) use scalar::{find, rfind};
+
+#[cfg(any(not(feature = "memchr"), test))]
+mod scalar {
+    pub(crate) fn find(needle: u8, haystack: &[u8]) -> Option<usize> {
+        haystack.iter().position(|&b| b == needle)
+    }
+
+    pub(crate) fn rfind(needle: u8, haystack: &[u8]) -> Option<usize> {
+        haystack.iter().rposition(|&b| b == needle)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Find, MagicString, MagicStringTrait};
+
+    #[test]
+    fn accelerated_matches_scalar() {
+        let slices = (0..2000)
+            .map(|i| ["", "a,b", "🍅", "long stretch without any commas", ",", "é\n"][i % 6])
+            .collect::<Vec<_>>();
+        let string = MagicString::new(&slices);
+        let expected = slices.concat();
+
+        for needle in [b',', b'\n', b'a', b'x', "🍅".as_bytes()[2]] {
+            for slice in slices.iter().map(|s| s.as_bytes()) {
+                assert_eq!(find(needle, slice), scalar::find(needle, slice));
+                assert_eq!(rfind(needle, slice), scalar::rfind(needle, slice));
+            }
+            let bytes = expected.as_bytes();
+            assert_eq!(string.find_byte(needle), scalar::find(needle, bytes));
+            assert_eq!(string.rfind_byte(needle), scalar::rfind(needle, bytes));
+            let tail = scalar::find(needle, &bytes[1000..]);
+            assert_eq!(string.get(1000..).find_byte(needle), tail);
+        }
+
+        for c in [',', '\n', 'x', '🍅'] {
+            assert_eq!(string.find(c), expected.find(c));
+            assert_eq!(string.rfind(c), expected.rfind(c));
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use core::fmt;
+
+use crate::MagicStringTrait;
+
+/// A string that remembers its length, see [`Cached::new`]
+///
+/// The length of a [`Concat`](crate::Concat) is the sum of the lengths of its sides,
+/// so a deeply nested one walks the whole tree every time it is asked. Wrapping the
+/// tree only makes its own `len` a lookup: splitting a `Concat` asks its left side
+/// for its length, so for `split_at` and `get` to stop walking the tree the left
+/// side of every `Concat` on the way down has to be wrapped as well. For a plain
+/// [`MagicString`](crate::MagicString) this isn't worth it.
+#[derive(Clone, Copy)]
+pub struct Cached<T> {
+    inner: T,
+    len: usize,
+}
+
+impl<'a, T: MagicStringTrait<'a>> Cached<T> {
+    /// Wrap `inner`, computing its length once.
+    /// ```
+    /// use magicstring::{Cached, MagicString, MagicStringTrait};
+    /// let input = ["ab", "c"];
+    /// let string = MagicString::new(&input);
+    /// let tree = Cached::new(string.concat("de").concat(string));
+    /// assert_eq!(tree.len(), 8);
+    /// assert_eq!(tree.get(2..6).to_string(), "cdea".to_string());
+    /// ```
+    pub fn new(inner: T) -> Self {
+        Self { len: inner.len(), inner }
+    }
+}
+
+impl<T> Cached<T> {
+    /// The wrapped string
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<'a, T: MagicStringTrait<'a>> MagicStringTrait<'a> for Cached<T> {
+    type Iter = T::Iter;
+    type Bytes = T::Bytes;
+    type Chars = T::Chars;
+    type CharIndices = T::CharIndices;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> Self::Iter {
+        self.inner.iter()
+    }
+
+    fn bytes(&self) -> Self::Bytes {
+        self.inner.bytes()
+    }
+
+    fn chars(&self) -> Self::Chars {
+        self.inner.chars()
+    }
+
+    fn char_indices(&self) -> Self::CharIndices {
+        self.inner.char_indices()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // The lengths of both halves follow from the index
+    fn split_at(&self, index: usize) -> (Self, Self) {
+        let (left, right) = self.inner.split_at(index);
+        let left = Self { inner: left, len: index };
+        (left, Self { inner: right, len: self.len - index })
+    }
+
+    fn trim_start_matches_fn(&self, pred: impl FnMut(char) -> bool) -> Self {
+        Self::new(self.inner.trim_start_matches_fn(pred))
+    }
+
+    fn trim_end_matches_fn(&self, pred: impl FnMut(char) -> bool) -> Self {
+        Self::new(self.inner.trim_end_matches_fn(pred))
+    }
+
+    fn pop(&mut self) -> Option<char> {
+        let c = self.inner.pop()?;
+        self.len -= c.len_utf8();
+        Some(c)
+    }
+}
+
+impl<'a, T: MagicStringTrait<'a> + fmt::Display> fmt::Display for Cached<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<'a, T: MagicStringTrait<'a> + fmt::Debug> fmt::Debug for Cached<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tracked::{Counts, Tracked};
+    use crate::MagicString;
+
+    #[test]
+    fn same_as_uncached() {
+        let s = ["a🍅", "", " b"];
+        let string = MagicString::new(&s);
+        let tree = string.concat(" c ").concat(string.concat("d\t"));
+        let cached = Cached::new(tree);
+        let expected = tree.to_string();
+
+        assert_eq!(cached.len(), tree.len());
+        assert_eq!(cached.to_string(), expected);
+        for index in (0..=expected.len()).filter(|&i| expected.is_char_boundary(i)) {
+            let (left, right) = cached.split_at(index);
+            assert_eq!(left.len(), index);
+            assert_eq!(left.to_string(), expected[..index]);
+            assert_eq!(right.len(), expected.len() - index);
+            assert_eq!(right.to_string(), expected[index..]);
+            assert_eq!(cached.get(index..).len(), tree.get(index..).len());
+        }
+
+        let trimmed = cached.trim();
+        assert_eq!(trimmed.len(), tree.trim().len());
+        assert_eq!(trimmed.to_string(), expected.trim());
+        assert_eq!(cached.get(5..).trim_start().len(), expected[5..].trim_start().len());
+
+        let mut popped = cached;
+        let mut expected = expected;
+        while let Some(c) = popped.pop() {
+            assert_eq!(Some(c), expected.pop());
+            assert_eq!(popped.len(), expected.len());
+        }
+        assert!(popped.is_empty());
+        assert_eq!(popped.into_inner().len(), 0);
+    }
+
+    #[test]
+    fn split_without_measuring() {
+        let counts = Counts::default();
+        let lens = &counts.lens;
+        let s = ["ab", "c"];
+        let leaf = Tracked::new(MagicString::new(&s), &counts);
+
+        let tree = leaf.concat(leaf).concat(leaf).concat(leaf);
+        assert_eq!(tree.get(4..8).to_string(), "bcab");
+        let uncached = lens.replace(0);
+
+        // Only the root is wrapped, so the left sides are still measured on the way down
+        let root = Cached::new(tree);
+        lens.set(0);
+        assert_eq!(root.get(4..8).to_string(), "bcab");
+        assert!(lens.get() > 0 && lens.get() < uncached, "{} {uncached}", lens.get());
+
+        let left = Cached::new(Cached::new(Cached::new(leaf).concat(leaf)).concat(leaf));
+        let tree = Cached::new(left.concat(leaf));
+        lens.set(0);
+        assert_eq!(tree.get(4..8).to_string(), "bcab");
+        assert_eq!(lens.get(), 0);
+    }
+}
@@ -0,0 +1,113 @@
+use core::iter::Rev;
+
+/// Char indices shifted by a fixed offset.
+/// Used to report indices relative to a larger string, like the right side of a
+/// [`Concat`](crate::Concat).
+#[derive(Clone, Debug)]
+pub struct OffsetCharIndices<I> {
+    inner: I,
+    offset: usize,
+}
+
+impl<I> OffsetCharIndices<I> {
+    pub(crate) fn new(inner: I, offset: usize) -> Self {
+        Self { inner, offset }
+    }
+}
+
+impl<I> Iterator for OffsetCharIndices<I>
+where
+    I: Iterator<Item = (usize, char)>,
+{
+    type Item = (usize, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, c) = self.inner.next()?;
+        Some((index + self.offset, c))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<I> DoubleEndedIterator for OffsetCharIndices<I>
+where
+    I: DoubleEndedIterator<Item = (usize, char)>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (index, c) = self.inner.next_back()?;
+        Some((index + self.offset, c))
+    }
+}
+
+/// Char indices walked from the end of the string, see
+/// [`MagicStringTrait::rev_char_indices`](crate::MagicStringTrait::rev_char_indices).
+/// The index of each char is found by counting back from the length of the string.
+#[derive(Clone, Debug)]
+pub struct RevCharIndices<C> {
+    chars: Rev<C>,
+    end: usize,
+}
+
+impl<C> RevCharIndices<C> {
+    pub(crate) fn new(chars: Rev<C>, len: usize) -> Self {
+        Self { chars, end: len }
+    }
+}
+
+impl<C> Iterator for RevCharIndices<C>
+where
+    C: DoubleEndedIterator<Item = char>,
+{
+    type Item = (usize, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.chars.next()?;
+        self.end -= c.len_utf8();
+        Some((self.end, c))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chars.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn forward() {
+        let mut indices = OffsetCharIndices::new("a🍅b".char_indices(), 10);
+        assert_eq!(indices.next(), Some((10, 'a')));
+        assert_eq!(indices.next(), Some((11, '🍅')));
+        assert_eq!(indices.next(), Some((15, 'b')));
+        assert_eq!(indices.next(), None);
+    }
+
+    #[test]
+    fn backward() {
+        let indices = OffsetCharIndices::new("a🍅b".char_indices(), 3);
+        let actual = indices.rev().collect::<Vec<_>>();
+        let expected = vec![(8, 'b'), (4, '🍅'), (3, 'a')];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn both_ends() {
+        let mut indices = OffsetCharIndices::new("abc".char_indices(), 1);
+        assert_eq!(indices.next_back(), Some((3, 'c')));
+        assert_eq!(indices.next(), Some((1, 'a')));
+        assert_eq!(indices.next_back(), Some((2, 'b')));
+        assert_eq!(indices.next(), None);
+    }
+
+    #[test]
+    fn rev() {
+        let indices = RevCharIndices::new("a🍅b".chars().rev(), 6);
+        let actual = indices.collect::<Vec<_>>();
+        let expected = vec![(5, 'b'), (1, '🍅'), (0, 'a')];
+        assert_eq!(expected, actual);
+    }
+}
@@ -1,7 +1,14 @@
-use std::iter::Chain;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::Chain;
+use core::ops::Add;
 
-use crate::MagicStringTrait;
+use unicode_width::UnicodeWidthStr;
 
+use crate::charindices::OffsetCharIndices;
+use crate::{hash_bytes, MagicString, MagicStringTrait};
+
+/// Two strings joined together, see [`MagicStringTrait::concat`]
 #[derive(Clone, Copy)]
 pub struct Concat<L, R> {
     left: L,
@@ -9,6 +16,7 @@ pub struct Concat<L, R> {
 }
 
 impl<L, R> Concat<L, R> {
+    /// Join `left` and `right` into one string.
     pub fn new(left: L, right: R) -> Self {
         Self { left, right }
     }
@@ -22,7 +30,7 @@ where
     type Iter = Chain<L::Iter, R::Iter>;
     type Bytes = Chain<L::Bytes, R::Bytes>;
     type Chars = Chain<L::Chars, R::Chars>;
-    type CharIndices = Chain<L::CharIndices, R::CharIndices>;
+    type CharIndices = Chain<L::CharIndices, OffsetCharIndices<R::CharIndices>>;
 
     fn len(&self) -> usize {
         self.left.len() + self.right.len()
@@ -32,7 +40,7 @@ where
         self.left.iter().chain(self.right.iter())
     }
 
-    fn bytes(self) -> Self::Bytes {
+    fn bytes(&self) -> Self::Bytes {
         self.left.bytes().chain(self.right.bytes())
     }
 
@@ -40,48 +48,45 @@ where
         self.left.chars().chain(self.right.chars())
     }
 
-    // TODO: This doesn't work because the indices in the right side are not updated
     fn char_indices(&self) -> Self::CharIndices {
-        self.left.char_indices().chain(self.right.char_indices())
+        let right = OffsetCharIndices::new(self.right.char_indices(), self.left.len());
+        self.left.char_indices().chain(right)
     }
 
     fn is_empty(&self) -> bool {
         self.left.is_empty() && self.right.is_empty()
     }
 
-    // TODO: This is weird each output has to be a Concat<L, R>
+    // Only the side the index falls in is split, the other side is kept whole.
+    // Both halves must be a `Concat<L, R>`, so the side missing from a half is
+    // filled with an empty split.
     fn split_at(&self, index: usize) -> (Self, Self) {
-        if index < self.left.len() {
-            let (left_of_left, right_of_left) = self.left.split_at(index);
-            let (left_of_right, right_of_right) = self.right.split_at(0);
-            (
-                Concat::new(left_of_left, left_of_right),
-                Concat::new(right_of_left, right_of_right),
-            )
+        let left_len = self.left.len();
+        if index <= left_len {
+            let (left, middle) = self.left.split_at(index);
+            let (empty, _) = self.right.split_at(0);
+            (Concat::new(left, empty), Concat::new(middle, self.right))
         } else {
-            let (left_of_left, right_of_left) = self.left.split_at(0);
-            let (left_of_right, right_of_right) = self.right.split_at(index - self.left.len());
-            (
-                Concat::new(right_of_left, left_of_right),
-                Concat::new(left_of_left, right_of_right),
-            )
+            let (middle, right) = self.right.split_at(index - left_len);
+            let (empty, _) = self.left.split_at(0);
+            (Concat::new(self.left, middle), Concat::new(empty, right))
         }
     }
 
-    fn trim_start(&self) -> Self {
-        let left = self.left.trim_start();
+    fn trim_start_matches_fn(&self, mut pred: impl FnMut(char) -> bool) -> Self {
+        let left = self.left.trim_start_matches_fn(&mut pred);
         let right = if left.is_empty() {
-            self.right.trim_start()
+            self.right.trim_start_matches_fn(&mut pred)
         } else {
             self.right
         };
         Concat::new(left, right)
     }
 
-    fn trim_end(&self) -> Self {
-        let right = self.right.trim_end();
+    fn trim_end_matches_fn(&self, mut pred: impl FnMut(char) -> bool) -> Self {
+        let right = self.right.trim_end_matches_fn(&mut pred);
         let left = if right.is_empty() {
-            self.left.trim_end()
+            self.left.trim_end_matches_fn(&mut pred)
         } else {
             self.left
         };
@@ -93,6 +98,99 @@ where
     }
 }
 
+impl<'a, L, R> fmt::Display for Concat<L, R>
+where
+    Self: MagicStringTrait<'a>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for slice in self.iter() {
+            write!(f, "{slice}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, L, R> fmt::Debug for Concat<L, R>
+where
+    Self: MagicStringTrait<'a>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for slice in self.iter() {
+            write!(f, "{slice:?}")?;
+        }
+        Ok(())
+    }
+}
+
+// Equal when the logical bytes are, no matter how either side is made up
+impl<'a, 'b, L, R, L2, R2> PartialEq<Concat<L2, R2>> for Concat<L, R>
+where
+    Self: MagicStringTrait<'a>,
+    Concat<L2, R2>: MagicStringTrait<'b>,
+{
+    fn eq(&self, other: &Concat<L2, R2>) -> bool {
+        self.len() == other.len() && self.bytes().eq(other.bytes())
+    }
+}
+
+impl<'a, L, R> Eq for Concat<L, R> where Self: MagicStringTrait<'a> {}
+
+impl<'a, L, R> Hash for Concat<L, R>
+where
+    Self: MagicStringTrait<'a>,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_bytes(self, state);
+    }
+}
+
+impl<'a, 'b, L, R> PartialEq<MagicString<'b>> for Concat<L, R>
+where
+    Self: MagicStringTrait<'a>,
+{
+    fn eq(&self, other: &MagicString<'b>) -> bool {
+        self.len() == other.len() && self.bytes().eq(other.bytes())
+    }
+}
+
+impl<'a, 'b, L, R> PartialEq<Concat<L, R>> for MagicString<'b>
+where
+    Concat<L, R>: MagicStringTrait<'a>,
+{
+    fn eq(&self, other: &Concat<L, R>) -> bool {
+        other == self
+    }
+}
+
+impl<'a, L, R, Rhs> Add<Rhs> for Concat<L, R>
+where
+    Self: MagicStringTrait<'a>,
+    Rhs: MagicStringTrait<'a>,
+{
+    type Output = Concat<Self, Rhs>;
+
+    fn add(self, rhs: Rhs) -> Self::Output {
+        self.concat(rhs)
+    }
+}
+
+// Measured slice by slice, as `&str` halves don't implement the trait themselves
+impl<'a, L, R> UnicodeWidthStr for Concat<L, R>
+where
+    L: MagicStringTrait<'a>,
+    R: MagicStringTrait<'a>,
+{
+    fn width(&self) -> usize {
+        let width = |s: &str| s.width();
+        self.left.iter().map(width).sum::<usize>() + self.right.iter().map(width).sum::<usize>()
+    }
+
+    fn width_cjk(&self) -> usize {
+        let width = |s: &str| s.width_cjk();
+        self.left.iter().map(width).sum::<usize>() + self.right.iter().map(width).sum::<usize>()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{MagicString, MagicStringTrait};
@@ -108,6 +206,45 @@ mod test {
     //     assert_eq!(actual, expected);
     // }
 
+    #[test]
+    fn remove_across_slices() {
+        let s = ["ab", "", "c🍅", "de"];
+        let string = MagicString::new(&s);
+        let expected = s.concat();
+
+        // Spans crossing one or more slice boundaries
+        for (start, end) in [(1, 3), (1, 7), (3, 8), (0, 9), (2, 2), (7, 9)] {
+            let mut expected = expected.clone();
+            expected.replace_range(start..end, "");
+            let removed = string.remove(start..end);
+            assert_eq!(removed.to_string(), expected, "{start}..{end}");
+            assert_eq!(removed.len(), expected.len());
+        }
+
+        assert_eq!(string.remove(..3).to_string(), "🍅de");
+        assert_eq!(string.remove(3..).to_string(), "abc");
+        assert_eq!(string.get(1..8).remove(1..6).to_string(), "bd");
+
+        // A whole slice in the middle
+        let s = ["some", "silly", "text"];
+        let removed = MagicString::new(&s).remove(4..9);
+        assert_eq!(removed.chars().collect::<String>(), "sometext");
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_inside_char() {
+        let s = ["a🍅"];
+        MagicString::new(&s).remove(1..3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_out_of_bounds() {
+        let s = ["ab", "c"];
+        MagicString::new(&s).remove(2..4);
+    }
+
     #[test]
     fn remove_middle() {
         let s = ["some", "silly", "text"];
@@ -145,22 +282,94 @@ mod test {
         assert_eq!(line_actual, tree_actual);
     }
 
-    // TODO: This doesn't work
-    // #[test]
-    // fn char_indices() {
-    //     let left = ["a", "🍅", "b"];
-    //     let right = ["c", "d"];
-    //     let left = MagicString::new(&left);
-    //     let right = MagicString::new(&right);
-    //     let string = left.concat(right);
-    //     let mut chars = string.char_indices();
-    //     assert_eq!(chars.next().unwrap(), (0, 'a'));
-    //     assert_eq!(chars.next().unwrap(), (1, '🍅'));
-    //     assert_eq!(chars.next().unwrap(), (5, 'b'));
-    //     // TODO: The wrong starts here
-    //     assert_eq!(chars.next().unwrap(), (6, 'c'));
-    //     assert_eq!(chars.next().unwrap(), (7, 'd'));
-    // }
+    #[test]
+    fn char_indices() {
+        let left = ["a", "🍅", "b"];
+        let right = ["c", "d"];
+        let left = MagicString::new(&left);
+        let right = MagicString::new(&right);
+        let string = left.concat(right);
+        let mut chars = string.char_indices();
+        assert_eq!(chars.next().unwrap(), (0, 'a'));
+        assert_eq!(chars.next().unwrap(), (1, '🍅'));
+        assert_eq!(chars.next().unwrap(), (5, 'b'));
+        assert_eq!(chars.next().unwrap(), (6, 'c'));
+        assert_eq!(chars.next().unwrap(), (7, 'd'));
+        assert!(chars.next().is_none());
+    }
+
+    #[test]
+    fn nested_char_indices() {
+        let a = ["é"];
+        let b = ["x", "🍅"];
+        let c = ["y"];
+        let a = MagicString::new(&a);
+        let b = MagicString::new(&b);
+        let c = MagicString::new(&c);
+        let actual = a.concat(b).concat(c).char_indices().collect::<Vec<_>>();
+        let expected = "éx🍅y".char_indices().collect::<Vec<_>>();
+        assert_eq!(expected, actual);
+
+        let actual = a.concat(b.concat(c)).char_indices().collect::<Vec<_>>();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn add() {
+        let s = ["hi"];
+        let s = MagicString::new(&s);
+        let actual = (s + "!").chars().collect::<String>();
+        let expected = "hi!".to_string();
+        assert_eq!(actual, expected);
+
+        let a = ["a", "b"];
+        let b = ["c"];
+        let a = MagicString::new(&a);
+        let b = MagicString::new(&b);
+        let actual = (a + "x" + b).chars().collect::<String>();
+        let expected = "abxc".to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn bytes_twice() {
+        let left = ["a", "b"];
+        let right = ["c"];
+        let left = MagicString::new(&left);
+        let right = MagicString::new(&right);
+        let string = left.concat(right);
+        let first = string.bytes().collect::<Vec<_>>();
+        let second = string.bytes().collect::<Vec<_>>();
+        assert_eq!(first, b"abc".to_vec());
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn display_and_debug() {
+        let flat = ["a", "b", "c\n"];
+        let left = ["a", "b"];
+        let right = ["c\n"];
+        let flat = MagicString::new(&flat);
+        let left = MagicString::new(&left);
+        let right = MagicString::new(&right);
+        let string = left.concat(right);
+        assert_eq!(format!("{string}"), format!("{flat}"));
+        assert_eq!(format!("{string:?}"), format!("{flat:?}"));
+    }
+
+    #[test]
+    fn add_matches_concat() {
+        let a = ["a", "b"];
+        let b = ["c"];
+        let c = ["d", "e"];
+        let a = MagicString::new(&a);
+        let b = MagicString::new(&b);
+        let c = MagicString::new(&c);
+        let expected = a.concat(b).concat(c).to_string();
+        assert_eq!((a + b + c).to_string(), expected);
+        assert_eq!((a + (b + c)).to_string(), expected);
+        assert_eq!((a + b + "x" + c).to_string(), "abcxde".to_string());
+    }
 
     #[test]
     fn collect() {
@@ -193,6 +402,32 @@ mod test {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn split_reconstructs() {
+        let a = ["01", "2"];
+        let b = ["34", "5"];
+        let a = MagicString::new(&a);
+        let b = MagicString::new(&b);
+        let string = a.concat(b).concat("67");
+        for index in 0..=string.len() {
+            let (left, right) = string.split_at(index);
+            let actual = left.concat(right).chars().collect::<String>();
+            assert_eq!(actual, "01234567".to_string());
+            assert_eq!(left.len(), index);
+        }
+
+        // No empty pieces are added for the side that wasn't split
+        for index in [0, 1, 4, 7, 8] {
+            let (left, right) = string.split_at(index);
+            assert!(left.iter().all(|s| !s.is_empty()), "split at {index}");
+            assert!(right.iter().all(|s| !s.is_empty()), "split at {index}");
+        }
+
+        let (left, right) = string.split_at(1);
+        assert_eq!(left.iter().collect::<Vec<_>>(), vec!["0"]);
+        assert_eq!(right.iter().collect::<Vec<_>>(), vec!["1", "2", "34", "5", "67"]);
+    }
+
     #[test]
     fn small_trims() {
         let left = ["  "];
@@ -235,4 +470,127 @@ mod test {
         let expected = "T".to_string();
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn trim_matches_fn() {
+        let left = ["!!ab?"];
+        let right = ["?cd", "!!"];
+        let left = MagicString::new(&left);
+        let right = MagicString::new(&right);
+        let s = left.concat(right);
+        let actual = s.trim_matches_fn(|c| !c.is_alphanumeric()).chars().collect::<String>();
+        let expected = "ab??cd".to_string();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn eq() {
+        let flat = ["abc", "def"];
+        let flat = MagicString::new(&flat);
+        let left = ["a", "bc"];
+        let right = ["d", "", "ef"];
+        let concat = MagicString::new(&left).concat(MagicString::new(&right));
+
+        assert_eq!(concat, flat);
+        assert_eq!(flat, concat);
+        assert_eq!(concat, "ab".concat("cd").concat("ef"));
+        assert_eq!(concat, "abcdef".concat(""));
+        assert_ne!(concat, flat.get(..5));
+        assert_ne!(flat.get(1..), concat);
+        assert_ne!(concat, "abcdeg".concat(""));
+    }
+
+    #[test]
+    fn get() {
+        use core::ops::Bound;
+
+        let first = ["ab", "", "c"];
+        let last = ["🍅", "de"];
+        let concat = MagicString::new(&first).concat("fg").concat(MagicString::new(&last));
+        let expected = "abcfg🍅de";
+
+        assert_eq!(concat.get(1..10).to_string(), &expected[1..10]);
+        assert_eq!(concat.get(1..=8).to_string(), &expected[1..=8]);
+        assert_eq!(concat.get(3..).to_string(), &expected[3..]);
+        assert_eq!(concat.get(..5).to_string(), &expected[..5]);
+        assert_eq!(concat.get(..=4).to_string(), &expected[..=4]);
+        assert_eq!(concat.get(..).to_string(), expected);
+        let bounds = (Bound::Excluded(2), Bound::Included(8));
+        assert_eq!(concat.get(bounds).to_string(), &expected[3..9]);
+
+        // Ranges within a single part, and empty ranges on either side of a seam
+        assert_eq!(concat.get(3..5).to_string(), "fg");
+        assert_eq!(concat.get(5..9).to_string(), "🍅");
+        assert!(concat.get(3..3).is_empty());
+        assert!(concat.get(5..5).is_empty());
+        assert_eq!(concat.get(2..9).get(1..3).to_string(), "fg");
+    }
+
+    #[test]
+    fn insert_str() {
+        let s = ["ab", "", "c🍅", "d"];
+        let string = MagicString::new(&s);
+        let expected = s.concat();
+
+        for index in [0, 1, 2, 3, 7, 8] {
+            let mut expected = expected.clone();
+            expected.insert_str(index, "XY");
+            let inserted = string.insert_str(index, "XY");
+            assert_eq!(inserted.to_string(), expected);
+            assert_eq!(inserted.len(), expected.len());
+        }
+
+        // Edits can be stacked, and inserting into a view stays within it
+        let edited = string.insert_str(8, "!").insert_str(0, "> ");
+        assert_eq!(edited.to_string(), "> abc🍅d!");
+        assert_eq!(string.get(1..3).insert_str(1, "-").to_string(), "b-c");
+        assert_eq!(MagicString::default().insert_str(0, "x").to_string(), "x");
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_str_inside_char() {
+        let s = ["a🍅"];
+        MagicString::new(&s).insert_str(2, "x");
+    }
+
+    #[test]
+    fn width() {
+        use unicode_width::UnicodeWidthStr;
+
+        let flat = ["a日本", "語\u{00A1}🍅"];
+        let flat = MagicString::new(&flat);
+        let left = ["a日", "本"];
+        let concat = MagicString::new(&left).concat("語").concat("\u{00A1}🍅");
+
+        assert_eq!(concat.width(), flat.width());
+        assert_eq!(concat.width(), 10);
+        // The inverted exclamation mark is ambiguous, so wider in CJK contexts
+        assert_eq!(concat.width_cjk(), flat.width_cjk());
+        assert_eq!(concat.width_cjk(), 11);
+        assert_eq!("".concat(MagicString::default()).width(), 0);
+    }
+
+    #[test]
+    fn hash() {
+        use core::hash::BuildHasher;
+        use std::collections::hash_map::RandomState;
+        use std::collections::HashSet;
+
+        let left = ["ab", "c"];
+        let right = ["", "de"];
+        let concat = MagicString::new(&left).concat(MagicString::new(&right));
+        let flat = ["abcde"];
+        let flat = MagicString::new(&flat);
+
+        // Same hash as the flat string, so either can be used as the key
+        let state = RandomState::new();
+        assert_eq!(state.hash_one(concat), state.hash_one(flat));
+
+        let mut set = HashSet::new();
+        set.insert(concat);
+        let (left, right) = flat.split_at(1);
+        assert!(set.contains(&left.concat(right)));
+        assert!(!set.contains(&right.concat(left)));
+    }
 }
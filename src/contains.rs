@@ -1,6 +1,8 @@
-use crate::MagicStringTrait;
+use crate::find::find_needle;
+use crate::{MagicString, MagicStringTrait};
 
-/// Checks if a [`MagicString`] contains either a [`char`] or a slice of chars.
+/// Checks if a [`MagicString`] contains either a [`char`], a slice of chars, a `&str`,
+/// a char matching a predicate or another [`MagicString`].
 pub trait Contains<P> {
     /// Does the string contain the pattern?
     fn contains(&self, pat: P) -> bool;
@@ -18,10 +20,27 @@ impl<'a, T: MagicStringTrait<'a>> Contains<&[char]> for T {
     }
 }
 
+impl<'a, T: MagicStringTrait<'a>, F: FnMut(char) -> bool> Contains<F> for T {
+    fn contains(&self, mut pat: F) -> bool {
+        self.iter().any(|s| s.contains(&mut pat))
+    }
+}
+
+impl<'a, 'b, T: MagicStringTrait<'a>> Contains<&'b str> for T {
+    fn contains(&self, pat: &'b str) -> bool {
+        find_needle(self, &pat, 0).is_some()
+    }
+}
+
+impl<'a, 'b, T: MagicStringTrait<'a>> Contains<MagicString<'b>> for T {
+    fn contains(&self, pat: MagicString<'b>) -> bool {
+        find_needle(self, &pat, 0).is_some()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::MagicString;
 
     #[test]
     fn contains_char() {
@@ -45,4 +64,45 @@ mod test {
         assert!(string.contains(['a', 'x'].as_slice()));
         assert!(!string.contains(['y', 'x'].as_slice()));
     }
+
+    #[test]
+    fn contains_str() {
+        let s = ["ab", "cd"];
+        let string = MagicString::new(&s);
+        assert!(string.contains("bc"));
+        assert!(string.contains("abcd"));
+        assert!(string.contains(""));
+        assert!(!string.contains("abcde"));
+        assert!(!string.contains("ac"));
+    }
+
+    #[test]
+    fn contains_fn() {
+        let s = ["ab", "c1"];
+        let string = MagicString::new(&s);
+        assert!(string.contains(|c: char| c.is_ascii_digit()));
+        assert!(!string.contains(char::is_whitespace));
+        assert!(!string.get(..3).contains(|c: char| c.is_ascii_digit()));
+    }
+
+    #[test]
+    fn contains_magic_string() {
+        let s = ["xa", "b", "", "cd", "e"];
+        let string = MagicString::new(&s);
+        let needles: [&[&str]; 5] = [&["ab", "c"], &["a", "bcde"], &["", "b", "cd"], &["xabcde"], &[]];
+        for needle in needles {
+            assert!(string.contains(MagicString::new(needle)), "{needle:?}");
+        }
+
+        let needles: [&[&str]; 3] = [&["ab", "d"], &["xabcdef"], &["e", "x"]];
+        for needle in needles {
+            assert!(!string.contains(MagicString::new(needle)), "{needle:?}");
+        }
+
+        // The match straddles the boundary between the two halves of a split haystack
+        let needle = ["bc"];
+        let (left, right) = string.split_at(3);
+        assert!(left.concat(right).contains(MagicString::new(&needle)));
+        assert!(!left.contains(MagicString::new(&needle)));
+    }
 }
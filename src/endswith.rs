@@ -0,0 +1,105 @@
+use crate::{Concat, MagicString, MagicStringTrait, Repeat};
+
+/// Checks if a [`MagicString`] ends with either a [`char`], a `&str`, a char
+/// matching a predicate or another magic string.
+pub trait EndsWith<P> {
+    /// Does the string end with the pattern?
+    fn ends_with(&self, pat: P) -> bool;
+}
+
+// Compares the chars of both strings from the end, no matter how they are
+// split into slices
+fn ends_with<'a, 'b>(
+    haystack: &impl MagicStringTrait<'a>,
+    needle: &impl MagicStringTrait<'b>,
+) -> bool {
+    // Too long to be a suffix, no need to walk the string from the end
+    if needle.len() > haystack.len() {
+        return false;
+    }
+    let mut chars = haystack.rchars();
+    needle.rchars().all(|c| chars.next() == Some(c))
+}
+
+impl<'a, T: MagicStringTrait<'a>> EndsWith<char> for T {
+    fn ends_with(&self, pat: char) -> bool {
+        self.chars().next_back() == Some(pat)
+    }
+}
+
+impl<'a, T: MagicStringTrait<'a>, F: FnMut(char) -> bool> EndsWith<F> for T {
+    fn ends_with(&self, pat: F) -> bool {
+        self.chars().next_back().is_some_and(pat)
+    }
+}
+
+impl<'a, 'b, T: MagicStringTrait<'a>> EndsWith<&'b str> for T {
+    fn ends_with(&self, pat: &'b str) -> bool {
+        ends_with(self, &pat)
+    }
+}
+
+impl<'a, 'b, T: MagicStringTrait<'a>> EndsWith<MagicString<'b>> for T {
+    fn ends_with(&self, pat: MagicString<'b>) -> bool {
+        ends_with(self, &pat)
+    }
+}
+
+impl<'a, 'b, T, L, R> EndsWith<Concat<L, R>> for T
+where
+    T: MagicStringTrait<'a>,
+    Concat<L, R>: MagicStringTrait<'b>,
+{
+    fn ends_with(&self, pat: Concat<L, R>) -> bool {
+        ends_with(self, &pat)
+    }
+}
+
+impl<'a, 'b, T, U> EndsWith<Repeat<U>> for T
+where
+    T: MagicStringTrait<'a>,
+    Repeat<U>: MagicStringTrait<'b>,
+{
+    fn ends_with(&self, pat: Repeat<U>) -> bool {
+        ends_with(self, &pat)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ends_with_char() {
+        let s = ["a🍅", ""];
+        let string = MagicString::new(&s);
+        assert!(string.ends_with('🍅'));
+        assert!(!string.ends_with('a'));
+        assert!(string.ends_with(|c: char| !c.is_ascii()));
+        assert!(!MagicString::default().ends_with(|_| true));
+    }
+
+    #[test]
+    fn ends_with_str() {
+        let s = ["a", "bc", "d"];
+        let string = MagicString::new(&s);
+        assert!(string.ends_with("cd"));
+        assert!(string.ends_with("abcd"));
+        assert!(string.ends_with(""));
+        assert!(!string.ends_with("xabcd"));
+        assert!(!string.ends_with("c"));
+    }
+
+    #[test]
+    fn ends_with_magic_string() {
+        let s = ["ab", "cd"];
+        let string = MagicString::new(&s);
+        let suffix = ["b", "", "c", "d"];
+        let suffix = MagicString::new(&suffix);
+        assert!(string.ends_with(suffix));
+        assert!(!suffix.ends_with(string));
+        assert!(string.ends_with("a".concat(suffix)));
+        assert!(!string.ends_with("x".concat(suffix)));
+        assert!(string.ends_with(MagicString::new(&["d"]).repeat(1)));
+    }
+}
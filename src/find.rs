@@ -1,10 +1,31 @@
 use super::MagicStringTrait;
+use crate::bytesearch;
 
 
-/// Finds the position of either a [`char`] or a slice of chars.
+/// Finds the position of either a [`char`], a slice of chars, a `&str` or a char predicate.
 pub trait Find<P> {
     /// Find the pattern inside the string, starting from the beginning of the string
     fn find(&self, pat: P) -> Option<usize>;
+    /// Find the pattern inside the string, starting from the beginning of the string.
+    /// Returns the position of the match and the index of the slice it starts in.
+    ///
+    /// The index counts the slices given by [`MagicStringTrait::iter`], so for a view
+    /// made with `get` it is not the index into the array the string was made from.
+    fn find_fragment<'a>(&self, pat: P) -> Option<(usize, usize)>
+    where
+        Self: MagicStringTrait<'a>,
+    {
+        let pos = self.find(pat)?;
+        let mut offset = 0;
+        for (slice, s) in self.iter().enumerate() {
+            offset += s.len();
+            if offset > pos {
+                return Some((pos, slice));
+            }
+        }
+        // An empty match at the very end is in the last slice
+        Some((pos, self.iter().count().saturating_sub(1)))
+    }
     /// Find the pattern inside the string, starting from the end of the string
     fn rfind(&self, pat: P) -> Option<usize>;
 }
@@ -13,7 +34,12 @@ impl<'a, T: MagicStringTrait<'a>> Find<char> for T {
     fn find(&self, pat: char) -> Option<usize> {
         let mut offset = 0;
         for s in self.iter() {
-            match s.find(pat) {
+            // An ASCII char is a single byte that can't be part of another char
+            let pos = match pat.is_ascii() {
+                true => bytesearch::find(pat as u8, s.as_bytes()),
+                false => s.find(pat),
+            };
+            match pos {
                 Some(pos) => return Some(pos + offset),
                 None => offset += s.len(),
             }
@@ -26,7 +52,11 @@ impl<'a, T: MagicStringTrait<'a>> Find<char> for T {
         let mut offset = self.len();
         for s in self.iter().rev() {
             offset -= s.len();
-            match s.rfind(pat) {
+            let pos = match pat.is_ascii() {
+                true => bytesearch::rfind(pat as u8, s.as_bytes()),
+                false => s.rfind(pat),
+            };
+            match pos {
                 Some(pos) => return Some(pos + offset),
                 None => continue,
             }
@@ -62,10 +92,153 @@ impl<'a, T: MagicStringTrait<'a>> Find<&[char]> for T {
     }
 }
 
+impl<'a, 'b, T: MagicStringTrait<'a>> Find<&'b str> for T {
+    fn find(&self, pat: &'b str) -> Option<usize> {
+        find_needle(self, &pat, 0)
+    }
+
+    fn rfind(&self, pat: &'b str) -> Option<usize> {
+        rfind_needle(self, &pat, self.len())
+    }
+}
+
+impl<'a, T: MagicStringTrait<'a>, F: FnMut(char) -> bool> Find<F> for T {
+    fn find(&self, mut pat: F) -> Option<usize> {
+        let mut offset = 0;
+        for s in self.iter() {
+            match s.find(&mut pat) {
+                Some(pos) => return Some(pos + offset),
+                None => offset += s.len(),
+            }
+        }
+
+        None
+    }
+
+    fn rfind(&self, mut pat: F) -> Option<usize> {
+        self.char_indices().rev().find(|&(_, c)| pat(c)).map(|(pos, _)| pos)
+    }
+}
+
+// Find the first position at or after `start` where `needle` begins.
+// Matches may span any number of the haystack's inner slices.
+pub(crate) fn find_needle<'a, 'b>(
+    haystack: &impl MagicStringTrait<'a>,
+    needle: &impl MagicStringTrait<'b>,
+    start: usize,
+) -> Option<usize> {
+    let Some(first) = needle.bytes().next() else {
+        // Resumed searches start at 0, which is always in range
+        return (start == 0 || start <= haystack.len()).then_some(start);
+    };
+
+    let mut offset = 0;
+    let mut slices = haystack.iter();
+    while let Some(s) = slices.next() {
+        // Slices that end before `start` are passed over without looking at their bytes
+        let mut from = start.saturating_sub(offset);
+        let bytes = s.as_bytes();
+        while let Some(pos) = bytes.get(from..).and_then(|rest| bytesearch::find(first, rest)) {
+            let pos = from + pos;
+            // What is left of `slices` is everything after this slice
+            if matches_at(&bytes[pos..], slices.clone(), needle) {
+                return Some(offset + pos);
+            }
+            from = pos + 1;
+        }
+        offset += s.len();
+    }
+
+    None
+}
+
+// Find the last position where `needle` begins with the match ending at or
+// before `end`, checking candidate positions from the end of the haystack backwards.
+pub(crate) fn rfind_needle<'a, 'b>(
+    haystack: &impl MagicStringTrait<'a>,
+    needle: &impl MagicStringTrait<'b>,
+    end: usize,
+) -> Option<usize> {
+    let Some(first) = needle.bytes().next() else {
+        return (end <= haystack.len()).then_some(end);
+    };
+    let last_start = end.checked_sub(needle.len())?;
+
+    let mut offset = haystack.len();
+    let mut slice = haystack.iter().count();
+    for s in haystack.iter().rev() {
+        offset -= s.len();
+        slice -= 1;
+        for (pos, b) in s.bytes().enumerate().rev() {
+            if b == first && offset + pos <= last_start {
+                let after = haystack.iter().skip(slice + 1);
+                if matches_at(&s.as_bytes()[pos..], after, needle) {
+                    return Some(offset + pos);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// Does `needle` begin with `bytes`, followed by the bytes of the slices in `rest`?
+fn matches_at<'a, 'b>(
+    bytes: &[u8],
+    rest: impl Iterator<Item = &'a str>,
+    needle: &impl MagicStringTrait<'b>,
+) -> bool {
+    let bytes = bytes.iter().copied().chain(rest.flat_map(str::bytes));
+    bytes.take(needle.len()).eq(needle.bytes())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::MagicString;
+    use crate::tracked::{Counts, Tracked};
+    use crate::{MagicString, Matches, Split};
+
+    #[test]
+    fn searches_resume() {
+        let s = ["xa"; 100];
+        let counts = Counts::default();
+        let string = Tracked::new(MagicString::new(&s), &counts);
+
+        // Searching again from the start for every match would step over ~5000 slices
+        assert_eq!(string.match_indices('a').count(), 100);
+        assert!(counts.steps.get() < 500, "{}", counts.steps.get());
+
+        counts.steps.set(0);
+        assert_eq!(string.split("a").count(), 101);
+        assert!(counts.steps.get() < 500, "{}", counts.steps.get());
+    }
+
+    #[test]
+    fn find_from_skips() {
+        let s = ["a"; 100];
+        let counts = Counts::default();
+        let string = Tracked::new(MagicString::new(&s), &counts);
+
+        // Every 'a' is a candidate that has to be checked against the next slice.
+        // Skipping from the first slice again for each of them would step over ~3700
+        assert_eq!(string.find_from(50, "ab"), None);
+        assert!(counts.steps.get() < 300, "{}", counts.steps.get());
+        assert_eq!(string.find_from(50, "aa"), Some(50));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn replace_resumes() {
+        use crate::Replace;
+
+        let s = ["xa"; 100];
+        let counts = Counts::default();
+        let string = Tracked::new(MagicString::new(&s), &counts);
+        let mut out = String::new();
+        string.replace_into("xa", "-", &mut out);
+        assert_eq!(out, "-".repeat(100));
+        assert!(counts.steps.get() < 500, "{}", counts.steps.get());
+    }
 
     #[test]
     fn find_by_char() {
@@ -99,6 +272,39 @@ mod test {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn find_fragment() {
+        let input = ["ab", "cd"];
+        let string = MagicString::new(input.as_slice());
+        assert_eq!(string.find_fragment('c'), Some((2, 1)));
+        assert_eq!(string.find_fragment('b'), Some((1, 0)));
+        assert_eq!(string.find_fragment(['x', 'd'].as_slice()), Some((3, 1)));
+        assert_eq!(string.find_fragment('x'), None);
+
+        // Slices are counted from the start of the view
+        let input = ["ab", "", "cd", "ef"];
+        let string = MagicString::new(input.as_slice());
+        assert_eq!(string.find_fragment('e'), Some((4, 3)));
+        assert_eq!(string.get(3..).find_fragment('e'), Some((1, 1)));
+        assert_eq!(string.get(2..).find_fragment('c'), Some((0, 0)));
+        assert_eq!(string.find_fragment(""), Some((0, 0)));
+        assert_eq!(string.get(6..).find_fragment(""), Some((0, 0)));
+    }
+
+    #[test]
+    fn find_needle_across_slices() {
+        let input = ["ab", "c", "abc", "d"];
+        let string = MagicString::new(input.as_slice());
+        assert_eq!(find_needle(&string, &"bca", 0), Some(1));
+        assert_eq!(find_needle(&string, &"abc", 0), Some(0));
+        assert_eq!(find_needle(&string, &"abc", 1), Some(3));
+        assert_eq!(find_needle(&string, &"cd", 0), Some(5));
+        assert_eq!(find_needle(&string, &"abcd", 0), Some(3));
+        assert_eq!(find_needle(&string, &"abcde", 0), None);
+        assert_eq!(find_needle(&string, &"", 2), Some(2));
+        assert_eq!(find_needle(&string, &"", 8), None);
+    }
+
     #[test]
     fn rfind_by_char() {
         let s = ["12", "3$45", "6$7", "89"];
@@ -128,9 +334,68 @@ mod test {
         let substring = string.get(1..5);
         let pos = substring.rfind('3').unwrap();
 
-        let substring = substring.get(..pos);
+        let substring = substring.get(..=pos);
         let actual = format!("{substring}");
         let expected = String::from("123");
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn find_by_fn() {
+        let s = ["ab c", "d1", "2"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.find(|c: char| c.is_ascii_digit()), Some(5));
+        assert_eq!(string.find(char::is_whitespace), Some(2));
+        assert_eq!(string.find(char::is_uppercase), None);
+        assert_eq!(string.rfind(|c: char| c.is_ascii_digit()), Some(6));
+        assert_eq!(string.find_fragment(|c: char| c.is_ascii_digit()), Some((5, 1)));
+    }
+
+    #[test]
+    fn rfind_by_fn() {
+        let s = ["a1 b", "\t2c", "", "🍅d"];
+        let string = MagicString::new(&s);
+        let expected = s.concat();
+        assert_eq!(string.rfind(char::is_whitespace), expected.rfind(char::is_whitespace));
+        assert_eq!(string.rfind(|c: char| c.is_ascii_digit()), Some(5));
+        assert_eq!(string.rfind(|c: char| !c.is_ascii()), Some(7));
+        assert_eq!(string.rfind(char::is_uppercase), None);
+
+        // The last match of a view, not of the whole string
+        let view = string.get(1..5);
+        assert_eq!(view.rfind(char::is_whitespace), Some(3));
+        assert_eq!(view.rfind(|c: char| c.is_ascii_digit()), Some(0));
+        assert_eq!(MagicString::default().rfind(|_| true), None);
+    }
+
+    #[test]
+    fn find_by_str() {
+        let s = ["ab", "cab", "", "ca", "b"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.find("abc"), Some(0));
+        assert_eq!(string.find_fragment("bca"), Some((1, 0)));
+        assert_eq!(string.find_fragment("cab"), Some((2, 1)));
+        assert_eq!(string.find("abd"), None);
+        assert_eq!(string.find(""), Some(0));
+    }
+
+    #[test]
+    fn rfind_by_str() {
+        let s = ["ab", "cab", "", "ca", "b"];
+        let string = MagicString::new(&s);
+        let expected = s.concat();
+        for pat in ["ab", "abc", "ca", "cab", "bc", "b", "", "x", "abcabcab"] {
+            assert_eq!(string.rfind(pat), expected.rfind(pat), "{pat}");
+        }
+
+        // The last match spans three slices
+        let s = ["xab", "c", "d", "abc", "", "d"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.rfind("abcd"), Some(5));
+        assert_eq!(string.get(..8).rfind("abcd"), Some(1));
+
+        let s = ["a🍅", "🍅b"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.rfind("🍅"), Some(5));
+    }
 }
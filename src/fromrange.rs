@@ -1,4 +1,4 @@
-use core::ops::{Range, RangeInclusive, RangeFrom, RangeFull, RangeTo, RangeToInclusive};
+use core::ops::{Bound, Range, RangeInclusive, RangeFrom, RangeFull, RangeTo, RangeToInclusive};
 
 pub trait FromRange {
     fn into_start_end(self, max: usize) -> (usize, usize);
@@ -25,7 +25,7 @@ impl FromRange for RangeFull {
 impl FromRange for RangeInclusive<usize> {
     fn into_start_end(self, _max: usize) -> (usize, usize) {
         let (start, end) = self.into_inner();
-        (start, end + 1)
+        (start, end.saturating_add(1))
     }
 }
 
@@ -37,10 +37,25 @@ impl FromRange for RangeTo<usize> {
 
 impl FromRange for RangeToInclusive<usize> {
     fn into_start_end(self, _max: usize) -> (usize, usize) {
-        (0, self.end + 1)
+        (0, self.end.saturating_add(1))
     }
 }
 
+impl FromRange for (Bound<usize>, Bound<usize>) {
+    fn into_start_end(self, max: usize) -> (usize, usize) {
+        let start = match self.0 {
+            Bound::Included(start) => start,
+            Bound::Excluded(start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match self.1 {
+            Bound::Included(end) => end.saturating_add(1),
+            Bound::Excluded(end) => end,
+            Bound::Unbounded => max,
+        };
+        (start, end)
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -91,4 +106,44 @@ mod test {
         test_range(..=5, "012345");
         test_range(..=2, "012");
     }
+
+    #[test]
+    fn ranges_match_str() {
+        let s = ["a🍅", "", "bc", "é"];
+        let string = MagicString::new(&s);
+        let expected = s.concat();
+        let boundaries = (0..=expected.len()).filter(|&i| expected.is_char_boundary(i));
+
+        for i in boundaries.clone() {
+            assert_eq!(string.get(i..).to_string(), expected[i..]);
+            assert_eq!(string.get(..i).to_string(), expected[..i]);
+            if i > 0 {
+                // The last byte of the char before `i`
+                assert_eq!(string.get(..=i - 1).to_string(), expected[..=i - 1]);
+            }
+        }
+
+        // A full range is the whole string, made of the same slices
+        let full = string.get(..);
+        assert_eq!(full, string);
+        assert!(full.iter().eq(string.iter()));
+
+        let sub = string.get(1..7);
+        assert_eq!(sub.get(..).to_string(), expected[1..7]);
+        assert_eq!(sub.get(4..).to_string(), expected[5..7]);
+    }
+
+    #[test]
+    fn from_bounds() {
+        use Bound::*;
+        let expected = "012345";
+        let bounds = [Included(1), Excluded(1), Unbounded];
+        let ends = [Included(4), Excluded(4), Unbounded];
+        for start in bounds {
+            for end in ends {
+                test_range((start, end), &expected[(start, end)]);
+            }
+        }
+        test_range((Excluded(0), Included(2)), "12");
+    }
 }
@@ -0,0 +1,109 @@
+use unicode_segmentation::{GraphemeCursor, GraphemeIncomplete};
+
+use crate::MagicStringTrait;
+
+/// An iterator over the extended grapheme clusters of a string,
+/// see [`MagicStringTrait::graphemes`]
+pub struct Graphemes<'a, T: MagicStringTrait<'a>> {
+    string: T,
+    len: usize,
+    // The cursor is fed the slices one at a time, so clusters that span
+    // several slices are found without copying them
+    cursor: GraphemeCursor,
+    slices: T::Iter,
+    slice: &'a str,
+    slice_start: usize,
+    // What is left of the string, starting at byte `consumed`
+    rest: T,
+    consumed: usize,
+}
+
+impl<'a, T: MagicStringTrait<'a>> Graphemes<'a, T> {
+    pub(crate) fn new(string: T) -> Self {
+        let len = string.len();
+        let cursor = GraphemeCursor::new(0, len, true);
+        let mut slices = string.iter();
+        let slice = slices.find(|s| !s.is_empty()).unwrap_or_default();
+        Self { string, len, cursor, slices, slice, slice_start: 0, rest: string, consumed: 0 }
+    }
+
+    // The cursor needs to look further back than the current slice
+    fn provide_context(&mut self, end: usize) {
+        let mut start = 0;
+        for slice in self.string.iter() {
+            if !slice.is_empty() && start + slice.len() == end {
+                self.cursor.provide_context(slice, start);
+                return;
+            }
+            start += slice.len();
+        }
+    }
+}
+
+impl<'a, T: MagicStringTrait<'a>> Iterator for Graphemes<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        loop {
+            match self.cursor.next_boundary(self.slice, self.slice_start) {
+                Ok(Some(boundary)) => {
+                    // A cursor that is reused after being given context miscounts
+                    // regional indicators, so every cluster gets a fresh one
+                    self.cursor = GraphemeCursor::new(boundary, self.len, true);
+                    let (grapheme, rest) = self.rest.split_at(boundary - self.consumed);
+                    self.rest = rest;
+                    self.consumed = boundary;
+                    return Some(grapheme);
+                }
+                Ok(None) => return None,
+                Err(GraphemeIncomplete::NextChunk) => {
+                    self.slice_start += self.slice.len();
+                    self.slice = self.slices.find(|s| !s.is_empty())?;
+                }
+                Err(GraphemeIncomplete::PreContext(end)) => self.provide_context(end),
+                Err(GraphemeIncomplete::InvalidOffset | GraphemeIncomplete::PrevChunk) => {
+                    unreachable!("the cursor only moves forward through the slices")
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    use crate::{MagicString, MagicStringTrait};
+
+    #[test]
+    fn graphemes() {
+        let inputs: [&[&str]; 6] = [
+            &["ab", "c"],
+            // Combining marks in the next slice
+            &["e", "\u{301}\u{302}x"],
+            &["a", "\u{301}", "", "\u{302}"],
+            // Family emoji split at the zero width joiners
+            &["👨", "\u{200d}", "👩\u{200d}", "👧!"],
+            // Two flags, each split in half
+            &["\u{1F1F7}", "\u{1F1F8}\u{1F1EE}", "\u{1F1F4}"],
+            &["\r", "\n", "🍅"],
+        ];
+        for input in inputs {
+            let string = MagicString::new(input);
+            let expected = input.concat();
+            let actual = string.graphemes().map(|g| g.to_string()).collect::<Vec<_>>();
+            let expected = expected.graphemes(true).collect::<Vec<_>>();
+            assert_eq!(expected, actual, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn graphemes_of_empty() {
+        assert_eq!(MagicString::default().graphemes().count(), 0);
+        assert_eq!(MagicString::new(&["", ""]).graphemes().count(), 0);
+    }
+}
@@ -1,3 +1,4 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(missing_docs)]
 //! A zero allocations string type made up of string slices.
 //!
@@ -12,25 +13,89 @@
 //!
 //! To use [`MagicString::find`] and [`MagicString::contains`] 
 //! import `magicstring::{Find, Contains}`.
+//!
+//! The crate is `#![no_std]` when the default `std` feature is disabled.
+//! Helpers that allocate a `String` need the `alloc` feature.
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::Rev;
+use core::ops::Add;
+use core::str::FromStr;
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::collections::TryReserveError;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
 use core::str::Bytes as StdBytes;
 use core::str::CharIndices as StdCharIndices;
 use core::str::Chars as StdChars;
 
+#[cfg(feature = "alloc")]
+use unicode_width::UnicodeWidthChar;
 use unicode_width::UnicodeWidthStr;
 
+#[cfg(feature = "alloc")]
+mod builder;
+mod bytesearch;
+mod cached;
+#[cfg(feature = "alloc")]
+mod casing;
+mod charindices;
 mod contains;
+mod endswith;
 mod find;
 mod fromrange;
+#[cfg(feature = "unicode-segmentation")]
+mod graphemes;
+mod matches;
+mod parse;
+mod pattern;
+mod rechunk;
+#[cfg(feature = "alloc")]
+mod replace;
 mod sealed;
+#[cfg(feature = "serde")]
+mod serialize;
 mod concat;
+mod repeat;
+mod split;
+mod startswith;
+mod strslice;
+#[cfg(test)]
+mod tracked;
+#[cfg(all(feature = "unicode-segmentation", feature = "alloc"))]
+mod words;
+mod wrap;
 
+use charindices::RevCharIndices;
 use fromrange::FromRange;
 
+#[cfg(feature = "alloc")]
+pub use builder::MagicStringBuilder;
+pub use cached::Cached;
+pub use concat::Concat;
 pub use contains::Contains;
+pub use endswith::EndsWith;
 pub use find::Find;
-
-use self::concat::Concat;
+#[cfg(feature = "unicode-segmentation")]
+pub use graphemes::Graphemes;
+pub use matches::{MatchIndices, Matches, RMatchIndices};
+pub use parse::ParseError;
+pub use pattern::Pattern;
+pub use rechunk::ReChunk;
+pub use repeat::Repeat;
+pub use split::{Split, SplitAsciiWhitespace, SplitIter};
+pub use startswith::StartsWith;
+#[cfg(all(feature = "unicode-segmentation", feature = "alloc"))]
+pub use words::UnicodeWords;
+pub use wrap::{Wrap, WrapWords};
+#[cfg(feature = "alloc")]
+pub use replace::Replace;
 
 // -----------------------------------------------------------------------------
 //     - Slice offset -
@@ -49,26 +114,58 @@ enum Offset {
     StartEnd(usize, usize),
 }
 
+impl Offset {
+    fn new(start: usize, end: usize) -> Self {
+        match (start, end) {
+            (0, 0) => Offset::None,
+            (start, 0) => Offset::Start(start),
+            (0, end) => Offset::End(end),
+            (start, end) => Offset::StartEnd(start, end),
+        }
+    }
+
+    fn start(&self) -> usize {
+        match *self {
+            Offset::Start(start) | Offset::StartEnd(start, _) => start,
+            _ => 0,
+        }
+    }
+
+    fn end(&self) -> usize {
+        match *self {
+            Offset::End(end) | Offset::StartEnd(_, end) => end,
+            _ => 0,
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------
 //     - Magic iterator -
 //     It's not really magic
 // -----------------------------------------------------------------------------
 /// Iterator over the inner string slices
+#[derive(Clone, Debug)]
 pub struct MagicIter<'a> {
     inner: &'a [&'a str],
-    index: usize,
+    // The slices in `front..back` haven't been yielded yet
+    front: usize,
+    back: usize,
     offset: Offset,
 }
 
 impl<'a> MagicIter<'a> {
+    fn new(inner: &'a [&'a str], offset: Offset) -> Self {
+        Self { inner, front: 0, back: inner.len(), offset }
+    }
+
     fn next_by_index(&mut self, forward: bool) -> Option<&'a str> {
-        if self.index == self.inner.len() {
+        if self.front == self.back {
             return None;
         }
 
         let index = match forward {
-            true => self.index,
-            false => self.inner.len() - 1 - self.index,
+            true => self.front,
+            false => self.back - 1,
         };
 
         let len = self.inner[index].len();
@@ -83,7 +180,10 @@ impl<'a> MagicIter<'a> {
 
         let ret = &self.inner[index][start..end];
 
-        self.index += 1;
+        match forward {
+            true => self.front += 1,
+            false => self.back -= 1,
+        }
 
         Some(ret)
     }
@@ -95,6 +195,11 @@ impl<'a> Iterator for MagicIter<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         self.next_by_index(true)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
 }
 
 impl<'a> DoubleEndedIterator for MagicIter<'a> {
@@ -103,16 +208,34 @@ impl<'a> DoubleEndedIterator for MagicIter<'a> {
     }
 }
 
+// The number of slices left, empty ones included
+impl<'a> ExactSizeIterator for MagicIter<'a> {}
+
 /// Magic string trait
+///
+/// Implemented by [`MagicString`], by [`Concat`] (made with [`MagicStringTrait::concat`]),
+/// by [`Repeat`] (made with [`MagicStringTrait::repeat`])
+/// and by plain `&str`, so generic code can work with all of them.
+/// ```
+/// use magicstring::{MagicString, MagicStringTrait};
+/// fn total_len<'a>(s: &impl MagicStringTrait<'a>) -> usize {
+///     s.iter().map(str::len).sum()
+/// }
+///
+/// let input = ["012", "34"];
+/// let string = MagicString::new(&input);
+/// assert_eq!(total_len(&string), 5);
+/// assert_eq!(total_len(&string.concat("56")), 7);
+/// ```
 pub trait MagicStringTrait<'a>: Sized + Copy {
-    /// &str iterator type
-    type Iter: Iterator<Item=&'a str> + DoubleEndedIterator;
-    /// byte iterator type
+    /// &str iterator type, yielding the inner string slices
+    type Iter: Iterator<Item=&'a str> + DoubleEndedIterator + Clone;
+    /// byte iterator type, yielding the bytes across all inner slices
     type Bytes: Iterator<Item=u8>;
-    /// char iterator type
-    type Chars: Iterator<Item=char>;
-    /// (index, char) iterator type
-    type CharIndices: Iterator<Item=(usize, char)>;
+    /// char iterator type, yielding the chars across all inner slices
+    type Chars: Iterator<Item=char> + DoubleEndedIterator;
+    /// (index, char) iterator type, where the index is the byte position in the whole string
+    type CharIndices: Iterator<Item=(usize, char)> + DoubleEndedIterator;
 
     /// The total length of the string in bytes
     fn len(&self) -> usize {
@@ -123,7 +246,7 @@ pub trait MagicStringTrait<'a>: Sized + Copy {
     fn iter(&self) -> Self::Iter;
 
     /// An iterator over the bytes of the inner string slices
-    fn bytes(self) -> Self::Bytes;
+    fn bytes(&self) -> Self::Bytes;
 
     /// An iterator over the characters of the inner string slices
     fn chars(&self) -> Self::Chars;
@@ -131,6 +254,32 @@ pub trait MagicStringTrait<'a>: Sized + Copy {
     /// An iterator over the characters and their index (byte position) of the inner string slices
     fn char_indices(&self) -> Self::CharIndices;
 
+    /// The characters of the string, starting from the end.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["ab", "🍅c"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.rchars().collect::<String>(), "c🍅ba".to_string());
+    /// ```
+    fn rchars(&self) -> Rev<Self::Chars> {
+        self.chars().rev()
+    }
+
+    /// The characters of the string and their index (byte position), starting from the end.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["ab", "🍅c"];
+    /// let string = MagicString::new(&input);
+    /// let mut indices = string.rev_char_indices();
+    /// assert_eq!(indices.next(), Some((6, 'c')));
+    /// assert_eq!(indices.next(), Some((2, '🍅')));
+    /// ```
+    fn rev_char_indices(&self) -> RevCharIndices<Self::Chars> {
+        RevCharIndices::new(self.rchars(), self.len())
+    }
+
     /// Returns true if this string has a length of zero, otherwise false
     fn is_empty(&self) -> bool;
 
@@ -145,13 +294,57 @@ pub trait MagicStringTrait<'a>: Sized + Copy {
 
     /// Trim the start of the string from any white space characters.
     /// This will not work correctly with RTL
-    fn trim_start(&self) -> Self;
+    fn trim_start(&self) -> Self {
+        self.trim_start_matches_fn(char::is_whitespace)
+    }
 
     /// Trim the end of the string from any white space characters.
     /// This will not work correctly with RTL
-    fn trim_end(&self) -> Self;
+    fn trim_end(&self) -> Self {
+        self.trim_end_matches_fn(char::is_whitespace)
+    }
+
+    /// Remove a single `\n` or `\r\n` from the end of the string, leaving any other
+    /// whitespace alone.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["line \r", "\n"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.trim_newline().to_string(), "line ".to_string());
+    /// ```
+    fn trim_newline(&self) -> Self {
+        if self.last_char() != Some('\n') {
+            return *self;
+        }
+        let (line, _) = self.split_at(self.len() - 1);
+        match line.last_char() {
+            Some('\r') => line.split_at(line.len() - 1).0,
+            _ => line,
+        }
+    }
+
+    /// Trim characters from both ends of the string for as long as `pred` holds.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["!!ab?", "?cd!!"];
+    /// let string = MagicString::new(&input);
+    /// let trimmed = string.trim_matches_fn(|c| !c.is_alphanumeric());
+    /// assert_eq!(trimmed.to_string(), "ab??cd".to_string());
+    /// ```
+    fn trim_matches_fn(&self, mut pred: impl FnMut(char) -> bool) -> Self {
+        self.trim_start_matches_fn(&mut pred).trim_end_matches_fn(&mut pred)
+    }
+
+    /// Trim characters from the start of the string for as long as `pred` holds.
+    fn trim_start_matches_fn(&self, pred: impl FnMut(char) -> bool) -> Self;
+
+    /// Trim characters from the end of the string for as long as `pred` holds.
+    fn trim_end_matches_fn(&self, pred: impl FnMut(char) -> bool) -> Self;
 
-    /// Get a [`MagicString`] from a range.
+    /// Get a view of the part of the string in a byte range. Works the same for every
+    /// kind of magic string, a [`Concat`] is sliced into a smaller [`Concat`].
     /// ```
     /// use magicstring::MagicStringTrait;
     /// use magicstring::MagicString;
@@ -189,484 +382,3244 @@ pub trait MagicStringTrait<'a>: Sized + Copy {
     /// ```
     fn pop(&mut self) -> Option<char>;
 
-    /// Concats
-    fn concat<R: MagicStringTrait<'a>>(self, right: R) -> concat::Concat<Self, R> {
-        Concat::new(self, right)
-    }
-}
-
-// -----------------------------------------------------------------------------
-//     - Magic string -
-// -----------------------------------------------------------------------------
-/// A non allocating string made up of string slices.
-#[derive(Copy, Clone)]
-pub struct MagicString<'a> {
-    inner: &'a [&'a str],
-    offset: Offset,
-}
+    /// Get the `n`-th character (0-based) of the string.
+    ///
+    /// Whole slices are skipped using their character count, so only the slice
+    /// holding the character is walked char by char.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["aé", "🍅b"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.nth_char(2), Some('🍅'));
+    /// assert_eq!(string.nth_char(4), None);
+    /// ```
+    fn nth_char(&self, n: usize) -> Option<char> {
+        let mut remaining = n;
+        for slice in self.iter() {
+            let count = slice.chars().count();
+            if count <= remaining {
+                remaining -= count;
+                continue;
+            }
+            return slice.chars().nth(remaining);
+        }
 
-impl<'a> MagicString<'a> {
-    /// Create a new instance of a `MagicString` from string slices.
-    pub fn new(inner: &'a [&'a str]) -> Self {
-        Self { inner, offset: Offset::None }
+        None
     }
 
-    // Index is operating on processed slices
-    fn index(&self, index: usize) -> (usize, usize) {
+    /// Get the byte position of the `n`-th character (0-based) of the string.
+    /// One past the last character maps to [`MagicStringTrait::len`], like a cursor
+    /// at the end of the string, anything beyond that is `None`.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["aé", "🍅b"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.char_index_to_byte(2), Some(3));
+    /// assert_eq!(string.char_index_to_byte(4), Some(8));
+    /// assert_eq!(string.char_index_to_byte(5), None);
+    /// ```
+    fn char_index_to_byte(&self, n: usize) -> Option<usize> {
+        let mut remaining = n;
         let mut offset = 0;
-        for (slice_index, slice) in self.iter().enumerate() {
-            if index - offset > slice.len() {
+        for slice in self.iter() {
+            let count = slice.chars().count();
+            if count <= remaining {
+                remaining -= count;
                 offset += slice.len();
                 continue;
             }
-
-            return (slice_index, index - offset);
+            return slice.char_indices().nth(remaining).map(|(index, _)| offset + index);
         }
 
-        panic!("index out of range");
+        (remaining == 0).then_some(offset)
     }
 
-    fn from_split(offset: Offset, inner: &'a [&'a str]) -> Self {
-        Self { inner, offset }
+    /// Split the string in two after the first `n` characters, instead of at a byte index
+    /// like [`MagicStringTrait::split_at`]. When the string is shorter than `n` characters
+    /// the right half is empty.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["aé", "🍅b"];
+    /// let string = MagicString::new(&input);
+    /// let (left, right) = string.split_at_char(3);
+    /// assert_eq!(left.to_string(), "aé🍅".to_string());
+    /// assert_eq!(right.to_string(), "b".to_string());
+    /// ```
+    fn split_at_char(&self, n: usize) -> (Self, Self) {
+        let index = self.char_index_to_byte(n).unwrap_or_else(|| self.len());
+        self.split_at(index)
     }
-}
 
-impl<'a> MagicStringTrait<'a> for MagicString<'a> {
-    type Iter = MagicIter<'a>;
-    type Bytes = Bytes<'a>;
-    type Chars = Chars<'a>;
-    type CharIndices = CharIndices<'a>;
-    /// The total length of the string in bytes
-    fn len(&self) -> usize {
-        self.iter().map(|s| s.len()).sum()
-    }
+    /// Get a part of the string like [`MagicStringTrait::get`], but with the range counted
+    /// in characters instead of bytes. Ends past the last character are clamped to the
+    /// end of the string.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["aé", "🍅b"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.get_chars(1..3).to_string(), "é🍅".to_string());
+    /// assert_eq!(string.get_chars(3..).to_string(), "b".to_string());
+    /// assert_eq!(string.get_chars(2..100).to_string(), "🍅b".to_string());
+    /// ```
+    fn get_chars(&self, range: impl FromRange) -> Self {
+        let (start, end) = range.into_start_end(usize::MAX);
 
-    /// Produce an iterator over the inner string slices.
-    fn iter(&self) -> Self::Iter {
-        MagicIter { inner: self.inner, offset: self.offset, index: 0 }
+        let (ret, _) = self.split_at_char(end);
+        let (_, ret) = ret.split_at_char(start);
+        ret
     }
 
-    /// An iterator over the bytes of the inner string slices
-    fn bytes(self) -> Self::Bytes {
-        Bytes::new(self.iter())
+    /// Split the string in two like [`MagicStringTrait::split_at`], but returns `None`
+    /// when `index` is past the end of the string or not on a char boundary.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["a", "🍅b"];
+    /// let string = MagicString::new(&input);
+    /// let (left, right) = string.split_at_checked(5).unwrap();
+    /// assert_eq!(left.to_string(), "a🍅".to_string());
+    /// assert_eq!(right.to_string(), "b".to_string());
+    /// assert_eq!(string.split_at_checked(2), None);
+    /// assert_eq!(string.split_at_checked(7), None);
+    /// ```
+    fn split_at_checked(&self, index: usize) -> Option<(Self, Self)> {
+        self.is_char_boundary(index).then(|| self.split_at(index))
     }
 
-    /// An iterator over the characters of the inner string slices
-    fn chars(&self) -> Self::Chars {
-        Chars::new(self.iter())
-    }
+    /// The longest start of the string that fits in `max` columns on a terminal,
+    /// and whether anything had to be cut off. Wide characters that don't fit are
+    /// left out entirely.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["ab", "日本"];
+    /// let string = MagicString::new(&input);
+    /// let (prefix, truncated) = string.truncate_to_width(5);
+    /// assert_eq!(prefix.to_string(), "ab日".to_string());
+    /// assert!(truncated);
+    /// ```
+    fn truncate_to_width(&self, max: usize) -> (Self, bool) {
+        let mut width = 0;
+        let mut chars = self.char_indices().peekable();
+        while let Some((index, c)) = chars.next() {
+            // A U+FE0F after the char makes it wide, even when it starts the next slice
+            let mut buf = [0; 8];
+            let mut len = c.encode_utf8(&mut buf).len();
+            if let Some(&(_, '\u{FE0F}')) = chars.peek() {
+                len += '\u{FE0F}'.encode_utf8(&mut buf[len..]).len();
+            }
+            width += core::str::from_utf8(&buf[..len]).map_or(0, UnicodeWidthStr::width);
+            if width > max {
+                let (prefix, _) = self.split_at(index);
+                return (prefix, true);
+            }
+        }
 
-    /// An iterator over the characters and their index (byte position) of the inner string slices
-    fn char_indices(&self) -> Self::CharIndices {
-        CharIndices::new(self.iter())
+        (*self, false)
     }
 
-    /// Returns true if this string has a length of zero, otherwise false
-    fn is_empty(&self) -> bool {
-        self.len() == 0
+    /// Write the string into `out` followed by enough `fill` chars to make it `width`
+    /// columns wide on a terminal. A string that is already wide enough is written unchanged.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["日", "本"];
+    /// let string = MagicString::new(&input);
+    /// let mut out = String::new();
+    /// string.pad_end(6, '.', &mut out);
+    /// assert_eq!(out, "日本..");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn pad_end(&self, width: usize, fill: char, out: &mut String) {
+        let padding = padding(self, width, fill);
+        self.iter().for_each(|slice| out.push_str(slice));
+        out.extend(core::iter::repeat_n(fill, padding));
     }
 
-    /// Split the string in two:
-    fn split_at(&self, index: usize) -> (Self, Self) {
-        let (slice, index) = self.index(index);
-
-        let left = &self.inner[..=slice];
-        let right = &self.inner[slice..];
-
-        let left_len = left[left.len() - 1].len();
-        let left_offset = match self.offset {
-            Offset::Start(start) => {
-                // Silly bits
-                let end = left_len - start - index;
-                Offset::StartEnd(start, end)
-            }
-            Offset::StartEnd(start, end) => {
-                let lark = index + start + end;
-                Offset::StartEnd(start, end + left_len - lark)
-            }
-            _ => Offset::End(left_len - index),
-        };
-
-        let right_offset = match self.offset {
-            Offset::Start(start) => Offset::Start(start + index),
-            Offset::StartEnd(start, end) => Offset::StartEnd(start + index, end),
-            Offset::End(end) => Offset::StartEnd(index, end),
-            _ => Offset::Start(index),
-        };
-
-        (Self::from_split(left_offset, left), Self::from_split(right_offset, right))
+    /// Like [`MagicStringTrait::pad_end`], but the `fill` chars are written before the string.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["日", "本"];
+    /// let string = MagicString::new(&input);
+    /// let mut out = String::new();
+    /// string.pad_start(6, ' ', &mut out);
+    /// assert_eq!(out, "  日本");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn pad_start(&self, width: usize, fill: char, out: &mut String) {
+        out.extend(core::iter::repeat_n(fill, padding(self, width, fill)));
+        self.iter().for_each(|slice| out.push_str(slice));
     }
 
-    /// Trim any white space from the start and the end of the string.
-    /// Unlike [`&str`] this does not work for RTL.
-    fn trim(&self) -> Self {
-        self.trim_start().trim_end()
+    /// Write the string into `out` centered in `width` columns on a terminal, with
+    /// `fill` chars on both sides. When the padding can't be split evenly the extra
+    /// char goes on the right. A string that is already wide enough is written unchanged.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["日", "本"];
+    /// let string = MagicString::new(&input);
+    /// let mut out = String::new();
+    /// string.center(7, '*', &mut out);
+    /// assert_eq!(out, "*日本**");
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn center(&self, width: usize, fill: char, out: &mut String) {
+        let padding = padding(self, width, fill);
+        out.extend(core::iter::repeat_n(fill, padding / 2));
+        self.iter().for_each(|slice| out.push_str(slice));
+        out.extend(core::iter::repeat_n(fill, padding - padding / 2));
     }
 
-    /// Trim the start of the string from any white space characters.
-    /// This will not work correctly with RTL
-    fn trim_start(&self) -> Self {
-        let mut slice_index = 0;
-        let mut char_index = 0;
-
-        for (index, slice) in self.iter().enumerate() {
-            let trimmed = slice.trim_start();
-            if trimmed.is_empty() {
-                slice_index = index;
-                char_index = slice.len();
-                continue;
-            }
-            slice_index = index;
-            char_index = slice.len() - trimmed.len();
-            break;
-        }
+    /// Hard wrap the string into lines that fit in `width` columns on a terminal.
+    /// Lines are broken between characters, and a character wider than `width`
+    /// gets a line of its own.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["hello", " wor", "ld"];
+    /// let string = MagicString::new(&input);
+    /// let lines = string.wrap(4).map(|l| l.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(lines, vec!["hell", "o wo", "rld"]);
+    /// ```
+    fn wrap(&self, width: usize) -> Wrap<Self> {
+        Wrap::new(*self, width)
+    }
 
-        let offset = match self.offset {
-            Offset::End(end) => Offset::StartEnd(char_index, end),
-            Offset::Start(start) => Offset::Start(start + char_index),
-            Offset::StartEnd(start, end) => Offset::StartEnd(start + char_index, end),
-            _ => Offset::Start(char_index),
-        };
+    /// Wrap the string into lines that fit in `width` columns on a terminal, breaking
+    /// at whitespace where possible. Words longer than `width` are broken like
+    /// [`MagicStringTrait::wrap`] does, and the lines have no leading or trailing whitespace.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["the quick br", "own fox"];
+    /// let string = MagicString::new(&input);
+    /// let lines = string.wrap_words(10).map(|l| l.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(lines, vec!["the quick", "brown fox"]);
+    /// ```
+    fn wrap_words(&self, width: usize) -> WrapWords<Self> {
+        WrapWords::new(*self, width)
+    }
 
-        Self::from_split(offset, &self.inner[slice_index..])
+    /// Checks if the string is made up of at most one non-empty slice, in which case
+    /// [`MagicStringTrait::as_str`] succeeds.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["012", "", "345"];
+    /// let string = MagicString::new(&input);
+    /// assert!(string.get(1..3).is_contiguous());
+    /// assert!(!string.is_contiguous());
+    /// ```
+    fn is_contiguous(&self) -> bool {
+        self.iter().filter(|s| !s.is_empty()).nth(1).is_none()
     }
 
-    /// Trim the end of the string from any white space characters.
-    /// This will not work correctly with RTL
-    fn trim_end(&self) -> Self {
-        let mut slice_index = self.inner.len();
-        let mut char_index = 0;
-        let mut slice_len = 0;
-
-        for (i, slice) in self.iter().rev().enumerate() {
-            slice_index = self.inner.len() - i;
-            let trimmed = slice.trim_end();
-            if trimmed.is_empty() {
-                slice_len = slice.len();
-                char_index = 0;
-                continue;
-            }
-            slice_len = slice.len();
-            char_index = trimmed.len();
-            break;
+    /// Get the string as a single `&str` without copying, which is possible when
+    /// it's made up of at most one non-empty slice. Otherwise returns `None`.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["012", "345"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.get(1..3).as_str(), Some("12"));
+    /// assert_eq!(string.as_str(), None);
+    /// ```
+    fn as_str(&self) -> Option<&'a str> {
+        let mut slices = self.iter().filter(|s| !s.is_empty());
+        match (slices.next(), slices.next()) {
+            (None, _) => Some(""),
+            (Some(slice), None) => Some(slice),
+            _ => None,
         }
+    }
 
-        let offset = match self.offset {
-            Offset::Start(start) => Offset::StartEnd(start, slice_len - char_index),
-            Offset::End(end) => Offset::End(end + slice_len - char_index),
-            Offset::StartEnd(start, end) => Offset::StartEnd(start, end + slice_len - char_index),
-            Offset::None => Offset::End(slice_len - char_index),
-        };
-
-        Self::from_split(offset, &self.inner[..slice_index])
+    /// Parse the string into another type, like [`str::parse`]. Strings made up of
+    /// several slices are copied first, onto the stack when they are short enough.
+    /// Without the `alloc` feature, longer strings of several slices can't be parsed
+    /// and return [`ParseError::NotContiguous`].
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["12", "34"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.parse::<u32>(), Ok(1234));
+    /// assert!(string.parse::<u8>().is_err());
+    /// ```
+    fn parse<F: FromStr>(&self) -> Result<F, ParseError<F::Err>> {
+        parse::parse(self)
     }
 
-    /// Remove the last char from the string
+    /// Get the string as a [`Cow`], borrowing it when it's made up of a single
+    /// slice (see [`MagicStringTrait::as_str`]) and copying it otherwise.
     /// ```
+    /// use std::borrow::Cow;
     /// use magicstring::MagicStringTrait;
     /// use magicstring::MagicString;
     /// let input = ["012", "345"];
-    /// let mut string = MagicString::new(&input);
-    /// let c = string.pop().unwrap();
-    /// assert_eq!(c, '5');
-    /// assert_eq!(string.to_string(), "01234".to_string());
+    /// let string = MagicString::new(&input);
+    /// assert!(matches!(string.get(..3).to_cow(), Cow::Borrowed("012")));
+    /// assert_eq!(string.to_cow(), Cow::<str>::Owned("012345".to_string()));
     /// ```
-    fn pop(&mut self) -> Option<char> {
-        let c = self.iter().rev().next().and_then(|s| s.chars().last())?;
-
-        let remove = c.len_utf8();
-        let to = self.len() - remove;
-        *self = self.get(..to);
-
-        Some(c)
-    }
-}
+    #[cfg(feature = "alloc")]
+    fn to_cow(&self) -> Cow<'a, str> {
+        if let Some(slice) = self.as_str() {
+            return Cow::Borrowed(slice);
+        }
 
-// -----------------------------------------------------------------------------
-//     - Display -
-// -----------------------------------------------------------------------------
-impl<'a> fmt::Display for MagicString<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut string = String::with_capacity(self.len());
         for slice in self.iter() {
-            write!(f, "{slice}")?;
+            string.push_str(slice);
         }
-        Ok(())
+        Cow::Owned(string)
     }
-}
 
-// -----------------------------------------------------------------------------
-//     - Debug -
-// -----------------------------------------------------------------------------
-impl<'a> fmt::Debug for MagicString<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    /// Write the string into `w` one slice at a time, returning the number of bytes written.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["012", "345"];
+    /// let string = MagicString::new(&input);
+    /// let mut out = Vec::new();
+    /// assert_eq!(string.write_to(&mut out).unwrap(), 6);
+    /// assert_eq!(out, b"012345");
+    /// ```
+    #[cfg(feature = "std")]
+    fn write_to<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<usize> {
+        let mut written = 0;
         for slice in self.iter() {
-            write!(f, "{slice:?}")?;
+            w.write_all(slice.as_bytes())?;
+            written += slice.len();
         }
-        Ok(())
+        Ok(written)
     }
-}
 
-// -----------------------------------------------------------------------------
-//     - Unicode width -
-// -----------------------------------------------------------------------------
-impl<'a> UnicodeWidthStr for MagicString<'a> {
-    fn width(&self) -> usize {
+    /// Write the string into a [`fmt::Write`] one slice at a time, like appending it to a
+    /// [`String`] without making an intermediate one.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["012", "345"];
+    /// let string = MagicString::new(&input);
+    /// let mut out = String::from("> ");
+    /// string.write_into(&mut out).unwrap();
+    /// assert_eq!(out, "> 012345");
+    /// ```
+    fn write_into<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        self.iter().try_for_each(|slice| w.write_str(slice))
+    }
+
+    /// Call `f` with the bytes of every slice of the string in order, for when the bytes
+    /// can be processed in chunks like by a checksum. Empty slices are skipped.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["012", "", "345"];
+    /// let string = MagicString::new(&input);
+    /// let mut sum = 0;
+    /// string.for_each_slice(|bytes| sum += bytes.iter().map(|&b| b as u32).sum::<u32>());
+    /// assert_eq!(sum, string.bytes().map(|b| b as u32).sum::<u32>());
+    /// ```
+    fn for_each_slice<F: FnMut(&[u8])>(&self, mut f: F) {
+        self.iter().filter(|s| !s.is_empty()).for_each(|s| f(s.as_bytes()));
+    }
+
+    /// Copy the bytes of the string into the start of `out`, returning how many were written.
+    /// If `out` is too small nothing is copied, and the required length is returned as an error.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["012", "345"];
+    /// let string = MagicString::new(&input);
+    /// let mut buf = [0; 8];
+    /// assert_eq!(string.copy_to_slice(&mut buf), Ok(6));
+    /// assert_eq!(&buf[..6], b"012345");
+    /// assert_eq!(string.copy_to_slice(&mut buf[..4]), Err(6));
+    /// ```
+    fn copy_to_slice(&self, out: &mut [u8]) -> Result<usize, usize> {
+        let len = self.len();
+        if out.len() < len {
+            return Err(len);
+        }
+
+        let mut written = 0;
+        for slice in self.iter() {
+            out[written..written + slice.len()].copy_from_slice(slice.as_bytes());
+            written += slice.len();
+        }
+        Ok(written)
+    }
+
+    /// Get the byte at position `index`, or `None` if it's out of range.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["ab", "c"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.byte(2), Some(b'c'));
+    /// assert_eq!(string.byte(3), None);
+    /// ```
+    fn byte(&self, index: usize) -> Option<u8> {
+        let mut index = index;
+        for slice in self.iter() {
+            if index < slice.len() {
+                return Some(slice.as_bytes()[index]);
+            }
+            index -= slice.len();
+        }
+
+        None
+    }
+
+    /// The first char of the string, without walking past the first non-empty slice.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["", "🍅b", "c"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.first_char(), Some('🍅'));
+    /// ```
+    fn first_char(&self) -> Option<char> {
+        self.iter().find_map(|s| s.chars().next())
+    }
+
+    /// The last char of the string, found from the last non-empty slice.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["a", "b🍅", ""];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.last_char(), Some('🍅'));
+    /// ```
+    fn last_char(&self) -> Option<char> {
+        self.iter().rev().find_map(|s| s.chars().next_back())
+    }
+
+    /// The next char a parser reading the string would see, the same as
+    /// [`MagicStringTrait::first_char`]. The string isn't changed by peeking.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["", "(a", ")"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.peek_char(), Some('('));
+    /// assert_eq!(string.chars().next(), Some('('));
+    /// ```
+    fn peek_char(&self) -> Option<char> {
+        self.first_char()
+    }
+
+    /// The char that starts at byte position `index`, the same as
+    /// [`MagicStringTrait::char_at`]. The string isn't changed by peeking.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["a🍅", "b"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.peek_char_at(1), Some('🍅'));
+    /// assert_eq!(string.peek_char_at(5), Some('b'));
+    /// assert_eq!(string.peek_char_at(2), None);
+    /// assert_eq!(string.peek_char_at(6), None);
+    /// ```
+    fn peek_char_at(&self, index: usize) -> Option<char> {
+        self.char_at(index)
+    }
+
+    /// Split off the first char of the string, returning it and a view of the rest.
+    /// Returns `None` if the string is empty.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["🍅", "bc"];
+    /// let string = MagicString::new(&input);
+    /// let (first, rest) = string.split_first_char().unwrap();
+    /// assert_eq!(first, '🍅');
+    /// assert_eq!(rest.to_string(), "bc".to_string());
+    /// ```
+    fn split_first_char(&self) -> Option<(char, Self)> {
+        let first = self.first_char()?;
+        let (_, rest) = self.split_at(first.len_utf8());
+        Some((first, rest))
+    }
+
+    /// Get the char starting at byte position `index`.
+    /// Returns `None` if `index` is out of range or not on a char boundary.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["a", "🍅"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.char_at(1), Some('🍅'));
+    /// assert_eq!(string.char_at(2), None);
+    /// ```
+    fn char_at(&self, index: usize) -> Option<char> {
+        let mut index = index;
+        for slice in self.iter() {
+            if index < slice.len() {
+                if !slice.is_char_boundary(index) {
+                    return None;
+                }
+                return slice[index..].chars().next();
+            }
+            index -= slice.len();
+        }
+
+        None
+    }
+
+    /// Find the first match of a [`Pattern`] that starts at or after byte position `start`.
+    /// Unlike `get(start..).find(pat)` no sub-view is made, and the position is
+    /// relative to the start of the whole string.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["ab", "cab", "c"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.find_from(0, "bc"), Some(1));
+    /// assert_eq!(string.find_from(2, "bc"), Some(4));
+    /// assert_eq!(string.find_from(5, 'a'), None);
+    /// ```
+    fn find_from<P: Pattern>(&self, start: usize, pat: P) -> Option<usize> {
+        pat.find_in(self, start)
+    }
+
+    /// Split the string around the last match of a [`Pattern`], like [`str::rsplit_once`].
+    /// The first half is everything before the separator, the second everything after
+    /// it, so the second half never contains the separator.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["a.b", ".c"];
+    /// let string = MagicString::new(&input);
+    /// let (head, tail) = string.rsplit_once('.').unwrap();
+    /// assert_eq!(head.to_string(), "a.b".to_string());
+    /// assert_eq!(tail.to_string(), "c".to_string());
+    /// assert!(string.rsplit_once(',').is_none());
+    /// ```
+    fn rsplit_once<P: Pattern>(&self, sep: P) -> Option<(Self, Self)> {
+        let start = sep.rfind_in(self, self.len())?;
+        let (head, rest) = self.split_at(start);
+        let (_, tail) = rest.split_at(sep.match_len());
+        Some((head, tail))
+    }
+
+    /// Find the position of the first occurrence of a raw byte. The byte doesn't need
+    /// to be a whole char, so no UTF-8 decoding is done.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["a:b", ":c"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.find_byte(b':'), Some(1));
+    /// assert_eq!(string.find_byte(b'x'), None);
+    /// ```
+    fn find_byte(&self, b: u8) -> Option<usize> {
+        let mut offset = 0;
+        for slice in self.iter() {
+            match bytesearch::find(b, slice.as_bytes()) {
+                Some(pos) => return Some(offset + pos),
+                None => offset += slice.len(),
+            }
+        }
+
+        None
+    }
+
+    /// Find the position of the last occurrence of a raw byte, see [`MagicStringTrait::find_byte`].
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["a:b", ":c"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.rfind_byte(b':'), Some(3));
+    /// ```
+    fn rfind_byte(&self, b: u8) -> Option<usize> {
+        let mut offset = self.len();
+        for slice in self.iter().rev() {
+            offset -= slice.len();
+            if let Some(pos) = bytesearch::rfind(b, slice.as_bytes()) {
+                return Some(offset + pos);
+            }
+        }
+
+        None
+    }
+
+    /// Checks that `index` is on a char boundary, like [`str::is_char_boundary`].
+    /// The start and end of the string, and the seams between the inner slices,
+    /// are always boundaries.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["a", "🍅"];
+    /// let string = MagicString::new(&input);
+    /// assert!(string.is_char_boundary(1));
+    /// assert!(!string.is_char_boundary(2));
+    /// assert!(string.is_char_boundary(5));
+    /// ```
+    fn is_char_boundary(&self, index: usize) -> bool {
+        let mut index = index;
+        for slice in self.iter() {
+            if index < slice.len() {
+                return slice.is_char_boundary(index);
+            }
+            index -= slice.len();
+        }
+
+        index == 0
+    }
+
+    /// Compares the chars of the string with those of `other`, which must also
+    /// have the same number of chars.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["a", "bc"];
+    /// let string = MagicString::new(&input);
+    /// assert!(string.chars_eq("abc".chars()));
+    /// assert!(!string.chars_eq(['a', 'b']));
+    /// ```
+    fn chars_eq<I: IntoIterator<Item = char>>(&self, other: I) -> bool {
+        self.chars().eq(other)
+    }
+
+    /// Compares the chars of the string with those of `other`, skipping whitespace
+    /// on both sides. The comparison stops at the first mismatch.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["a b", "\tc "];
+    /// let string = MagicString::new(&input);
+    /// assert!(string.eq_ignoring_whitespace("ab\nc"));
+    /// assert!(!string.eq_ignoring_whitespace("a bd"));
+    /// ```
+    fn eq_ignoring_whitespace(&self, other: &str) -> bool {
+        let other = other.chars().filter(|c| !c.is_whitespace());
+        self.chars().filter(|c| !c.is_whitespace()).eq(other)
+    }
+
+    /// Checks that two strings are an ASCII case-insensitive match.
+    /// `other` can be a `&str` or another magic string.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["AbC", "dE"];
+    /// let string = MagicString::new(&input);
+    /// assert!(string.eq_ignore_ascii_case("abcde"));
+    /// ```
+    fn eq_ignore_ascii_case<'b>(&self, other: impl MagicStringTrait<'b>) -> bool {
+        self.len() == other.len()
+            && self.bytes().zip(other.bytes()).all(|(a, b)| a.eq_ignore_ascii_case(&b))
+    }
+
+    /// The number of lines in the string, the same as [`str::lines`] would give.
+    /// Lines end with `\n` or `\r\n`, and the last line doesn't need to end with either.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["a\nb", "\r\nc"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.count_lines(), 3);
+    /// ```
+    fn count_lines(&self) -> usize {
+        let newlines: usize = self.iter().map(|s| s.bytes().filter(|&b| b == b'\n').count()).sum();
+        let unterminated = self.chars().next_back().is_some_and(|c| c != '\n');
+        newlines + usize::from(unterminated)
+    }
+
+    /// Checks if all the chars of the string are ASCII, one slice at a time.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["ab", "cé"];
+    /// let string = MagicString::new(&input);
+    /// assert!(string.get(..3).is_ascii());
+    /// assert!(!string.is_ascii());
+    /// ```
+    fn is_ascii(&self) -> bool {
+        self.iter().all(str::is_ascii)
+    }
+
+    /// Like [`str::split_ascii_whitespace`], the pieces of the string between runs of
+    /// ASCII whitespace, as views into it. Uses [`char::is_ascii_whitespace`], so a
+    /// vertical tab is not whitespace.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["a \t", "\tb", " c"];
+    /// let string = MagicString::new(&input);
+    /// let pieces = string.split_ascii_whitespace().map(|p| p.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(pieces, vec!["a", "b", "c"]);
+    /// ```
+    fn split_ascii_whitespace(&self) -> SplitAsciiWhitespace<Self> {
+        SplitAsciiWhitespace::new(*self)
+    }
+
+    /// Cut the string into views of `size` bytes, whatever slices it is made up of.
+    /// A chunk ends early rather than in the middle of a char, and a char that is
+    /// larger than `size` gets a chunk of its own.
+    ///
+    /// Panics if `size` is 0.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["ab", "cdé", "f"];
+    /// let string = MagicString::new(&input);
+    /// let chunks = string.rechunk(3).map(|c| c.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(chunks, vec!["abc", "dé", "f"]);
+    /// ```
+    fn rechunk(&self, size: usize) -> ReChunk<Self> {
+        assert!(size != 0, "chunk size must be non-zero");
+        ReChunk::new(*self, size)
+    }
+
+    /// Checks if any char of the string is whitespace.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["ab", "c d"];
+    /// let string = MagicString::new(&input);
+    /// assert!(string.contains_whitespace());
+    /// assert!(!string.get(..3).contains_whitespace());
+    /// ```
+    fn contains_whitespace(&self) -> bool {
+        self.iter().any(|s| s.contains(char::is_whitespace))
+    }
+
+    /// Collect the string into a [`String`], returning an error instead of
+    /// aborting if the memory for it can't be reserved.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["012", "345"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.try_to_string().unwrap(), "012345".to_string());
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn try_to_string(&self) -> Result<String, TryReserveError> {
+        let mut string = String::new();
+        string.try_reserve(self.len())?;
+        for slice in self.iter() {
+            string.push_str(slice);
+        }
+        Ok(string)
+    }
+
+    /// Returns the lowercase equivalent of the string as a new [`String`].
+    /// Like [`str::to_lowercase`], a `'Σ'` at the end of a word becomes `'ς'`.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["HeLLo ", "ὈΔΥΣ", "ΣΕΎΣ"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.to_lowercase(), "hello ὀδυσσεύς".to_string());
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn to_lowercase(&self) -> String {
+        let mut string = String::with_capacity(self.len());
+        // Whether the last char that isn't case-ignorable was cased
+        let mut after_cased = false;
+        // Where a 'σ' was written that is final unless a cased char comes next
+        let mut maybe_final = None;
+        let make_final = |string: &mut String, index: usize| {
+            string.replace_range(index..index + 'σ'.len_utf8(), "ς");
+        };
+        for c in self.chars() {
+            if c == 'Σ' {
+                // A 'Σ' is cased, so one before it isn't final
+                maybe_final = after_cased.then_some(string.len());
+                string.push('σ');
+                after_cased = true;
+                continue;
+            }
+            string.extend(c.to_lowercase());
+            if casing::is_case_ignorable(c) {
+                continue;
+            }
+            after_cased = casing::is_cased(c);
+            if let Some(index) = maybe_final.take().filter(|_| !after_cased) {
+                make_final(&mut string, index);
+            }
+        }
+        if let Some(index) = maybe_final {
+            make_final(&mut string, index);
+        }
+        string
+    }
+
+    /// Returns the uppercase equivalent of the string as a new [`String`].
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["stra", "ße"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.to_uppercase(), "STRASSE".to_string());
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn to_uppercase(&self) -> String {
+        let mut string = String::with_capacity(self.len());
+        for c in self.chars() {
+            string.extend(c.to_uppercase());
+        }
+        string
+    }
+
+    /// Join two strings into a [`Concat`], without copying either of them.
+    /// The result is itself a [`MagicStringTrait`], so concats can be nested.
+    /// ```
+    /// use magicstring::{MagicString, MagicStringTrait};
+    /// let left = ["01", "2"];
+    /// let right = ["34"];
+    /// let left = MagicString::new(&left);
+    /// let right = MagicString::new(&right);
+    /// let string = left.concat(right).concat("5");
+    /// assert_eq!(string.to_string(), "012345".to_string());
+    /// ```
+    fn concat<R: MagicStringTrait<'a>>(self, right: R) -> Concat<Self, R> {
+        Concat::new(self, right)
+    }
+
+    /// An iterator over the extended grapheme clusters of the string, as views into it.
+    /// Clusters may span several of the inner slices.
+    /// Needs the `unicode-segmentation` feature.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["ne", "\u{301}e"];
+    /// let string = MagicString::new(&input);
+    /// let graphemes = string.graphemes().map(|g| g.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(graphemes, vec!["n", "e\u{301}", "e"]);
+    /// ```
+    #[cfg(feature = "unicode-segmentation")]
+    fn graphemes(&self) -> Graphemes<'a, Self> {
+        Graphemes::new(*self)
+    }
+
+    /// An iterator over the words of the string, as views into it. Words follow the
+    /// Unicode word boundary rules and may span several of the inner slices.
+    /// Needs the `unicode-segmentation` and `alloc` features, as finding the
+    /// boundaries copies the string once.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["Hello, wor", "ld! It's ", "3.5"];
+    /// let string = MagicString::new(&input);
+    /// let words = string.unicode_words().map(|w| w.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(words, vec!["Hello", "world", "It's", "3.5"]);
+    /// ```
+    #[cfg(all(feature = "unicode-segmentation", feature = "alloc"))]
+    fn unicode_words(&self) -> UnicodeWords<Self> {
+        UnicodeWords::new(*self)
+    }
+
+    /// Repeat the string `n` times as a [`Repeat`], without allocating.
+    /// ```
+    /// use magicstring::{MagicString, MagicStringTrait};
+    /// let input = ["ab", "c"];
+    /// let string = MagicString::new(&input).repeat(3);
+    /// assert_eq!(string.len(), 9);
+    /// assert_eq!(string.to_string(), "abcabcabc".to_string());
+    /// ```
+    fn repeat(&self, n: usize) -> Repeat<Self> {
+        Repeat::new(*self, n)
+    }
+
+    /// Insert `s` at byte position `index` without copying, by splitting the string
+    /// there and joining the halves back together around `s`.
+    ///
+    /// # Panics
+    /// Panics if `index` is not on a char boundary or is past the end of the string.
+    /// ```
+    /// use magicstring::{MagicString, MagicStringTrait};
+    /// let input = ["hello", " world"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.insert_str(5, ",").to_string(), "hello, world".to_string());
+    /// ```
+    fn insert_str(&self, index: usize, s: &'a str) -> Concat<Concat<Self, &'a str>, Self> {
+        assert!(self.is_char_boundary(index), "byte index {index} is not a char boundary");
+        let (left, right) = self.split_at(index);
+        left.concat(s).concat(right)
+    }
+
+    /// Remove a byte range from the string without copying, by joining what is
+    /// left on either side of it.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds, or doesn't start and end on char boundaries.
+    /// ```
+    /// use magicstring::{MagicString, MagicStringTrait};
+    /// let input = ["hello", ", world"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.remove(4..7).to_string(), "hellworld".to_string());
+    /// ```
+    fn remove(&self, range: impl FromRange) -> Concat<Self, Self> {
+        let (start, end) = range.into_start_end(self.len());
+        assert!(start <= end && end <= self.len(), "range {start}..{end} is out of bounds");
+        assert!(
+            self.is_char_boundary(start) && self.is_char_boundary(end),
+            "range {start}..{end} is not on char boundaries"
+        );
+        let (left, _) = self.split_at(start);
+        let (_, right) = self.split_at(end);
+        left.concat(right)
+    }
+}
+
+// -----------------------------------------------------------------------------
+//     - Magic string -
+// -----------------------------------------------------------------------------
+/// A non allocating string made up of string slices.
+#[derive(Copy, Clone)]
+pub struct MagicString<'a> {
+    // All the slices the string was created from.
+    // The string itself is made up of `backing[start..end]`.
+    backing: &'a [&'a str],
+    start: usize,
+    end: usize,
+    offset: Offset,
+    // Optional cumulative lengths of `backing`, see `with_prefix_sums`
+    sums: Option<&'a [usize]>,
+}
+
+impl<'a> MagicString<'a> {
+    /// Create a new instance of a `MagicString` from string slices.
+    pub fn new(inner: &'a [&'a str]) -> Self {
+        Self { backing: inner, start: 0, end: inner.len(), offset: Offset::None, sums: None }
+    }
+
+    /// Create a new instance of a `MagicString` that caches the cumulative lengths
+    /// of the slices in `sums`. This makes `len` constant time and lets `split_at`
+    /// and `get` find the slice to split with a binary search, which is worth it
+    /// for strings made up of many slices.
+    ///
+    /// `sums` must have room for one more element than `inner`.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["012", "34", "5"];
+    /// let mut sums = [0; 4];
+    /// let string = MagicString::with_prefix_sums(&input, &mut sums);
+    /// assert_eq!(string.len(), 6);
+    /// assert_eq!(string.get(2..5).to_string(), "234".to_string());
+    /// ```
+    pub fn with_prefix_sums(inner: &'a [&'a str], sums: &'a mut [usize]) -> Self {
+        assert_eq!(sums.len(), inner.len() + 1, "`sums` must be one longer than `inner`");
+
+        let mut total = 0;
+        sums[0] = 0;
+        for (sum, slice) in sums[1..].iter_mut().zip(inner) {
+            total += slice.len();
+            *sum = total;
+        }
+
+        Self { sums: Some(sums), ..Self::new(inner) }
+    }
+
+    /// Join two strings into one `MagicString` when they are next to each other
+    /// in the same slices, such as the two halves of [`MagicStringTrait::split_at`].
+    ///
+    /// Returns `None` when they can't be joined, in which case
+    /// [`MagicStringTrait::concat`] can be used instead.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["012", "345"];
+    /// let string = MagicString::new(&input);
+    /// let (left, right) = string.split_at(4);
+    /// let joined = left.try_concat(&right).unwrap();
+    /// assert_eq!(joined.to_string(), "012345".to_string());
+    /// assert!(right.try_concat(&left).is_none());
+    /// ```
+    pub fn try_concat(&self, other: &MagicString<'a>) -> Option<MagicString<'a>> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+        if !core::ptr::eq(self.backing, other.backing) {
+            return None;
+        }
+
+        let end = self.position(self.end - 1, self.backing[self.end - 1].len() - self.offset.end());
+        let start = other.position(other.start, other.offset.start());
+        if end != start {
+            return None;
+        }
+
+        let offset = Offset::new(self.offset.start(), other.offset.end());
+        Some(Self { start: self.start, end: other.end, offset, ..*self })
+    }
+
+    /// The processed slices of the string, each with the byte position it starts at.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["012", "34", "567"];
+    /// let string = MagicString::new(&input).get(1..7);
+    /// let segments = string.segments().collect::<Vec<_>>();
+    /// assert_eq!(segments, vec![(0, "12"), (2, "34"), (4, "56")]);
+    /// ```
+    pub fn segments(&self) -> Segments<'a> {
+        Segments { inner: self.iter(), offset: 0 }
+    }
+
+    /// The number of non-empty slices the string is made up of.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["012", "", "34", "567"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.num_slices(), 3);
+    /// assert_eq!(string.get(4..).num_slices(), 2);
+    /// ```
+    pub fn num_slices(&self) -> usize {
+        self.iter().filter(|s| !s.is_empty()).count()
+    }
+
+    /// Collect the string into a [`String`]. Unlike the [`ToString`] impl that goes
+    /// through [`fmt::Display`], the whole length is reserved up front.
+    /// ```
+    /// use magicstring::MagicString;
+    /// let input = ["012", "345"];
+    /// let string = MagicString::new(&input);
+    /// assert_eq!(string.to_string(), "012345".to_string());
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        let mut string = String::with_capacity(self.len());
+        self.iter().for_each(|slice| string.push_str(slice));
+        string
+    }
+
+    /// Grow the string to include the next `extra` slices of the array it was made from,
+    /// for when more of the array is filled in over time. What the string cut off of its
+    /// last slice is included again. Returns `None` if there aren't `extra` more slices.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["012", "34", "567"];
+    /// let string = MagicString::new(&input).get(..3);
+    /// assert_eq!(string.extend_to_slice(2).unwrap().to_string(), "01234567".to_string());
+    /// assert!(string.extend_to_slice(3).is_none());
+    /// ```
+    pub fn extend_to_slice(&self, extra: usize) -> Option<Self> {
+        let end = self.end.checked_add(extra).filter(|&end| end <= self.backing.len())?;
+        Some(Self { end, offset: Offset::new(self.offset.start(), 0), ..*self })
+    }
+
+    /// Shows how the string is laid out: the slices it is made up of, with how much is cut
+    /// off the start of the first one and the end of the last one. Unlike the [`fmt::Debug`]
+    /// impl of the string itself, this is meant for debugging the string, not its contents.
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["012", "345"];
+    /// let string = MagicString::new(&input).get(1..5);
+    /// let structure = format!("{:?}", string.debug_structure());
+    /// assert_eq!(structure, r#"["012"+start=1, "345"-end=1]"#);
+    /// ```
+    pub fn debug_structure(&self) -> impl fmt::Debug + 'a {
+        Structure(*self)
+    }
+
+    // The slices that make up the string, before the offset is applied
+    fn inner(&self) -> &'a [&'a str] {
+        &self.backing[self.start..self.end]
+    }
+
+    // Byte position of `index` in the `slice`th backing slice, counted from the
+    // start of all the backing slices
+    fn position(&self, slice: usize, index: usize) -> usize {
+        match self.sums {
+            Some(sums) => sums[slice] + index,
+            None => self.backing[..slice].iter().map(|s| s.len()).sum::<usize>() + index,
+        }
+    }
+
+    // Index is operating on processed slices
+    fn index(&self, index: usize) -> (usize, usize) {
+        if let Some(sums) = self.sums {
+            // Same as below, the first slice that ends at or after the index
+            let position = sums[self.start] + self.offset.start() + index;
+            assert!(position <= sums[self.end] - self.offset.end(), "index out of range");
+
+            let slice = sums[self.start + 1..=self.end].partition_point(|&sum| sum < position);
+            let slice_start = match slice {
+                0 => sums[self.start] + self.offset.start(),
+                _ => sums[self.start + slice],
+            };
+            return (slice, position - slice_start);
+        }
+
+        let mut offset = 0;
+        for (slice_index, slice) in self.iter().enumerate() {
+            if index - offset > slice.len() {
+                offset += slice.len();
+                continue;
+            }
+
+            return (slice_index, index - offset);
+        }
+
+        panic!("index out of range");
+    }
+
+    // Shorten the string to its first `new_len` bytes in place, without making
+    // the right half like `split_at` does. Empty slices after the end are dropped.
+    fn truncate_bytes(&mut self, new_len: usize) {
+        let (slice, index) = self.index(new_len);
+        // The start offset only applies to the first slice
+        let skipped = if slice == 0 { self.offset.start() } else { 0 };
+        let end = self.inner()[slice].len() - skipped - index;
+        *self = self.view(Offset::new(self.offset.start(), end), 0, slice + 1);
+    }
+
+    // A string made up of `inner()[start..end]`
+    fn view(&self, offset: Offset, start: usize, end: usize) -> Self {
+        let (start, end) = (self.start + start, self.start + end);
+        Self { start, end, offset, ..*self }
+    }
+
+    // Drop the empty slices at the start and the end, like what is left of a slice
+    // that was split at its end. Empty slices in between are kept.
+    fn strip_empty(&self) -> Self {
+        let leading = self.iter().take_while(|s| s.is_empty()).count();
+        if leading == self.inner().len() {
+            return Self::empty();
+        }
+        let trailing = self.iter().rev().take_while(|s| s.is_empty()).count();
+
+        // The offsets only stay if the slice they apply to does
+        let start = if leading == 0 { self.offset.start() } else { 0 };
+        let end = if trailing == 0 { self.offset.end() } else { 0 };
+        self.view(Offset::new(start, end), leading, self.inner().len() - trailing)
+    }
+
+    // An empty string that doesn't point into any slices
+    fn empty() -> Self {
+        Self { backing: &[], start: 0, end: 0, offset: Offset::None, sums: None }
+    }
+}
+
+impl<'a> MagicStringTrait<'a> for MagicString<'a> {
+    type Iter = MagicIter<'a>;
+    type Bytes = Bytes<'a>;
+    type Chars = Chars<'a>;
+    type CharIndices = CharIndices<'a>;
+    /// The total length of the string in bytes
+    fn len(&self) -> usize {
+        match self.sums {
+            Some(sums) => sums[self.end] - sums[self.start] - self.offset.start() - self.offset.end(),
+            None => self.iter().map(|s| s.len()).sum(),
+        }
+    }
+
+    /// Produce an iterator over the inner string slices.
+    fn iter(&self) -> Self::Iter {
+        MagicIter::new(self.inner(), self.offset)
+    }
+
+    /// An iterator over the bytes of the inner string slices
+    fn bytes(&self) -> Self::Bytes {
+        Bytes::new(self.iter())
+    }
+
+    /// An iterator over the characters of the inner string slices
+    fn chars(&self) -> Self::Chars {
+        Chars::new(self.iter())
+    }
+
+    /// An iterator over the characters and their index (byte position) of the inner string slices
+    fn char_indices(&self) -> Self::CharIndices {
+        CharIndices::new(self.iter())
+    }
+
+    /// Returns true if this string has a length of zero, otherwise false
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Split the string in two:
+    fn split_at(&self, index: usize) -> (Self, Self) {
+        debug_assert!(self.is_char_boundary(index), "byte index {index} is not a char boundary");
+
+        if index == 0 {
+            return (Self::empty(), self.strip_empty());
+        }
+
+        // Splitting at the end leaves an empty right half, which `strip_empty` turns into
+        // `Self::empty()`. Not comparing with `self.len()` keeps this from walking every slice.
+        let (slice, index) = self.index(index);
+
+        // The start offset only applies to the first slice, the end offset to the last
+        let skipped = if slice == 0 { self.offset.start() } else { 0 };
+        let position = skipped + index;
+        let left_offset = Offset::new(self.offset.start(), self.inner()[slice].len() - position);
+        let right_offset = Offset::new(position, self.offset.end());
+
+        let left = self.view(left_offset, 0, slice + 1);
+        let right = self.view(right_offset, slice, self.inner().len());
+        (left.strip_empty(), right.strip_empty())
+    }
+
+    /// Trim any white space from the start and the end of the string.
+    /// Unlike [`&str`] this does not work for RTL.
+    fn trim(&self) -> Self {
+        self.trim_start().trim_end()
+    }
+
+    /// Trim characters from the start of the string for as long as `pred` holds.
+    fn trim_start_matches_fn(&self, mut pred: impl FnMut(char) -> bool) -> Self {
+        // Count the trimmed bytes, passing over slices that are trimmed entirely
+        // (or were empty to begin with), and split there
+        let mut trimmed = 0;
+        for slice in self.iter() {
+            let rest = slice.trim_start_matches(&mut pred);
+            trimmed += slice.len() - rest.len();
+            if !rest.is_empty() {
+                break;
+            }
+        }
+
+        self.split_at(trimmed).1
+    }
+
+    /// Trim characters from the end of the string for as long as `pred` holds.
+    fn trim_end_matches_fn(&self, mut pred: impl FnMut(char) -> bool) -> Self {
+        let mut trimmed = 0;
+        for slice in self.iter().rev() {
+            let rest = slice.trim_end_matches(&mut pred);
+            trimmed += slice.len() - rest.len();
+            if !rest.is_empty() {
+                break;
+            }
+        }
+
+        self.split_at(self.len() - trimmed).0
+    }
+
+    /// Remove the last char from the string
+    /// ```
+    /// use magicstring::MagicStringTrait;
+    /// use magicstring::MagicString;
+    /// let input = ["012", "345"];
+    /// let mut string = MagicString::new(&input);
+    /// let c = string.pop().unwrap();
+    /// assert_eq!(c, '5');
+    /// assert_eq!(string.to_string(), "01234".to_string());
+    /// ```
+    fn pop(&mut self) -> Option<char> {
+        let c = self.last_char()?;
+        self.truncate_bytes(self.len() - c.len_utf8());
+        Some(c)
+    }
+}
+
+// -----------------------------------------------------------------------------
+//     - Into iterator -
+//     Iterates over the inner string slices, same as `iter`
+// -----------------------------------------------------------------------------
+impl<'a> IntoIterator for MagicString<'a> {
+    type Item = &'a str;
+    type IntoIter = MagicIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &MagicString<'a> {
+    type Item = &'a str;
+    type IntoIter = MagicIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// -----------------------------------------------------------------------------
+//     - Default -
+// -----------------------------------------------------------------------------
+impl Default for MagicString<'_> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+// -----------------------------------------------------------------------------
+//     - From -
+//     A `MagicString` borrows its slices, so a single slice has to be passed by
+//     reference as well: `(&slice).into()`
+// -----------------------------------------------------------------------------
+impl<'a> From<&'a [&'a str]> for MagicString<'a> {
+    fn from(inner: &'a [&'a str]) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl<'a, const N: usize> From<&'a [&'a str; N]> for MagicString<'a> {
+    fn from(inner: &'a [&'a str; N]) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl<'a> From<&'a &'a str> for MagicString<'a> {
+    fn from(slice: &'a &'a str) -> Self {
+        Self::new(core::slice::from_ref(slice))
+    }
+}
+
+// -----------------------------------------------------------------------------
+//     - Display -
+// -----------------------------------------------------------------------------
+impl<'a> fmt::Display for MagicString<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for slice in self.iter() {
+            write!(f, "{slice}")?;
+        }
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+//     - Debug -
+// -----------------------------------------------------------------------------
+impl<'a> fmt::Debug for MagicString<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for slice in self.iter() {
+            write!(f, "{slice:?}")?;
+        }
+        Ok(())
+    }
+}
+
+// The layout of a string, see `MagicString::debug_structure`
+struct Structure<'a>(MagicString<'a>);
+
+impl<'a> fmt::Debug for Structure<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.0.inner();
+        write!(f, "[")?;
+        for (i, slice) in inner.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{slice:?}")?;
+            let start = self.0.offset.start();
+            if i == 0 && start > 0 {
+                write!(f, "+start={start}")?;
+            }
+            let end = self.0.offset.end();
+            if i + 1 == inner.len() && end > 0 {
+                write!(f, "-end={end}")?;
+            }
+        }
+        write!(f, "]")
+    }
+}
+
+// -----------------------------------------------------------------------------
+//     - Ordering -
+//     Strings are compared by their bytes, no matter how they are split
+//     into slices. This matches the ordering of `str`.
+// -----------------------------------------------------------------------------
+impl<'a> PartialEq for MagicString<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.bytes().eq(other.bytes())
+    }
+}
+
+impl<'a> Eq for MagicString<'a> {}
+
+impl<'a> PartialOrd for MagicString<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for MagicString<'a> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.bytes().cmp(other.bytes())
+    }
+}
+
+// -----------------------------------------------------------------------------
+//     - Hash -
+//     Consistent with `Eq`: equal byte contents hash equally, however the
+//     string is split. The bytes are fed to the hasher in fixed size blocks
+//     that don't depend on where the slices end.
+// -----------------------------------------------------------------------------
+pub(crate) fn hash_bytes<'a, H: Hasher>(string: &impl MagicStringTrait<'a>, state: &mut H) {
+    let mut block = [0; 64];
+    let mut len = 0;
+    for mut bytes in string.iter().map(str::as_bytes) {
+        while !bytes.is_empty() {
+            let n = bytes.len().min(block.len() - len);
+            block[len..len + n].copy_from_slice(&bytes[..n]);
+            len += n;
+            bytes = &bytes[n..];
+            if len == block.len() {
+                state.write(&block);
+                len = 0;
+            }
+        }
+    }
+    state.write(&block[..len]);
+    // 0xff is never part of UTF-8, so a prefix followed by more data, like in a
+    // tuple, doesn't hash like the whole string
+    state.write_u8(0xff);
+}
+
+impl<'a> Hash for MagicString<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_bytes(self, state);
+    }
+}
+
+// -----------------------------------------------------------------------------
+//     - Add -
+//     `a + b` is the same as `a.concat(b)`
+// -----------------------------------------------------------------------------
+impl<'a, R: MagicStringTrait<'a>> Add<R> for MagicString<'a> {
+    type Output = Concat<Self, R>;
+
+    fn add(self, rhs: R) -> Self::Output {
+        self.concat(rhs)
+    }
+}
+
+// -----------------------------------------------------------------------------
+//     - Unicode width -
+// -----------------------------------------------------------------------------
+impl<'a> UnicodeWidthStr for MagicString<'a> {
+    fn width(&self) -> usize {
         self.iter().map(|s| s.width()).sum()
     }
 
-    fn width_cjk(&self) -> usize {
-        self.iter().map(|s| s.width_cjk()).sum()
+    fn width_cjk(&self) -> usize {
+        self.iter().map(|s| s.width_cjk()).sum()
+    }
+}
+
+// How many `fill` chars it takes to pad the string to `width` columns, without
+// going over when `fill` is a wide char
+#[cfg(feature = "alloc")]
+fn padding<'a>(string: &impl MagicStringTrait<'a>, width: usize, fill: char) -> usize {
+    let current: usize = string.iter().map(UnicodeWidthStr::width).sum();
+    let fill_width = fill.width().unwrap_or(0).max(1);
+    width.saturating_sub(current) / fill_width
+}
+
+// -----------------------------------------------------------------------------
+//     - Segments -
+// -----------------------------------------------------------------------------
+/// An iterator over the slices of the [`MagicString`] and where they start,
+/// see [`MagicString::segments`]
+#[derive(Clone, Debug)]
+pub struct Segments<'a> {
+    inner: MagicIter<'a>,
+    offset: usize,
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slice = self.inner.next()?;
+        let offset = self.offset;
+        self.offset += slice.len();
+        Some((offset, slice))
+    }
+}
+
+// -----------------------------------------------------------------------------
+//     - Bytes -
+// -----------------------------------------------------------------------------
+/// An iterator over the bytes of the [`MagicString`]
+#[derive(Clone, Debug)]
+pub struct Bytes<'a> {
+    inner: MagicIter<'a>,
+    current: Option<StdBytes<'a>>,
+}
+
+impl<'a> Bytes<'a> {
+    fn new(mut inner: MagicIter<'a>) -> Self {
+        let current = inner.next().map(|s| s.bytes());
+        Self { inner, current }
+    }
+}
+
+impl<'a> Iterator for Bytes<'a> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.current.as_mut()?.next() {
+            Some(s) => Some(s),
+            None => {
+                let slice = self.inner.next()?;
+                self.current = Some(slice.bytes());
+                self.next()
+            }
+        }
+    }
+
+    // Skips whole slices that end before the `n`th byte
+    fn nth(&mut self, mut n: usize) -> Option<Self::Item> {
+        loop {
+            let current = self.current.as_mut()?;
+            if n < current.len() {
+                return current.nth(n);
+            }
+            n -= current.len();
+            self.current = self.inner.next().map(str::bytes);
+        }
+    }
+
+    fn count(self) -> usize {
+        let current = self.current.map_or(0, |bytes| bytes.len());
+        current + self.inner.map(str::len).sum::<usize>()
+    }
+}
+
+// -----------------------------------------------------------------------------
+//     - Chars -
+// -----------------------------------------------------------------------------
+/// An iterator over the chars of the [`MagicString`]
+#[derive(Clone, Debug)]
+pub struct Chars<'a> {
+    inner: MagicIter<'a>,
+    // The slices being walked from either end, once they've been reached
+    front: Option<StdChars<'a>>,
+    back: Option<StdChars<'a>>,
+}
+
+impl<'a> Chars<'a> {
+    fn new(inner: MagicIter<'a>) -> Self {
+        Self { inner, front: None, back: None }
+    }
+}
+
+impl<'a> Iterator for Chars<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(c) = self.front.as_mut().and_then(Iterator::next) {
+                return Some(c);
+            }
+            // Once the slices run out, finish what is left of the back
+            let Some(slice) = self.inner.next() else {
+                return self.back.as_mut()?.next();
+            };
+            self.front = Some(slice.chars());
+        }
+    }
+
+    // The slices that haven't been reached are only measured when asked, so that
+    // making the iterator doesn't walk the whole string
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let front = self.front.as_ref().map_or(0, |c| c.as_str().len());
+        let back = self.back.as_ref().map_or(0, |c| c.as_str().len());
+        let rest = self.inner.clone().map(str::len).sum::<usize>();
+        let remaining = rest + front + back;
+        // Every char is between 1 and 4 bytes
+        (remaining.div_ceil(4), Some(remaining))
+    }
+
+    // Skips whole slices that end before the `n`th char
+    fn nth(&mut self, mut n: usize) -> Option<Self::Item> {
+        loop {
+            if let Some(front) = self.front.as_mut() {
+                let count = front.clone().count();
+                if n < count {
+                    return front.nth(n);
+                }
+                n -= count;
+                self.front = None;
+            }
+            let Some(slice) = self.inner.next() else {
+                return self.back.as_mut()?.nth(n);
+            };
+            self.front = Some(slice.chars());
+        }
+    }
+
+    // Counts whole slices at once rather than going through `next`
+    fn count(self) -> usize {
+        let front = self.front.map_or(0, Iterator::count);
+        let back = self.back.map_or(0, Iterator::count);
+        front + self.inner.map(|s| s.chars().count()).sum::<usize>() + back
+    }
+
+    // The last char is at the back, so there is no need to walk the rest
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Chars<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(c) = self.back.as_mut().and_then(DoubleEndedIterator::next_back) {
+                return Some(c);
+            }
+            let Some(slice) = self.inner.next_back() else {
+                return self.front.as_mut()?.next_back();
+            };
+            self.back = Some(slice.chars());
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+//     - Char indices -
+// -----------------------------------------------------------------------------
+/// An iterator over the characters and their index of the [`MagicString`]
+#[derive(Clone, Debug)]
+pub struct CharIndices<'a> {
+    inner: MagicIter<'a>,
+    // The slices being walked from either end and the position they start at
+    front: Option<(usize, StdCharIndices<'a>)>,
+    back: Option<(usize, StdCharIndices<'a>)>,
+    // The slices of `inner` that haven't been reached yet are in `offset..end`.
+    // `end` is only worked out once they are walked from the back.
+    offset: usize,
+    end: Option<usize>,
+}
+
+impl<'a> CharIndices<'a> {
+    fn new(inner: MagicIter<'a>) -> Self {
+        Self { inner, front: None, back: None, offset: 0, end: None }
+    }
+}
+
+impl<'a> Iterator for CharIndices<'a> {
+    type Item = (usize, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((start, indices)) = self.front.as_mut() {
+                if let Some((i, c)) = indices.next() {
+                    return Some((*start + i, c));
+                }
+            }
+            // Once the slices run out, finish what is left of the back
+            let Some(slice) = self.inner.next() else {
+                let (start, indices) = self.back.as_mut()?;
+                return indices.next().map(|(i, c)| (*start + i, c));
+            };
+            self.front = Some((self.offset, slice.char_indices()));
+            self.offset += slice.len();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let front = self.front.as_ref().map_or(0, |(_, indices)| indices.as_str().len());
+        let back = self.back.as_ref().map_or(0, |(_, indices)| indices.as_str().len());
+        let rest = self.inner.clone().map(str::len).sum::<usize>();
+        let remaining = rest + front + back;
+        // Every char is between 1 and 4 bytes
+        (remaining.div_ceil(4), Some(remaining))
+    }
+}
+
+impl<'a> DoubleEndedIterator for CharIndices<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((start, indices)) = self.back.as_mut() {
+                if let Some((i, c)) = indices.next_back() {
+                    return Some((*start + i, c));
+                }
+            }
+            let end = match self.end {
+                Some(end) => end,
+                None => self.offset + self.inner.clone().map(str::len).sum::<usize>(),
+            };
+            let Some(slice) = self.inner.next_back() else {
+                let (start, indices) = self.front.as_mut()?;
+                return indices.next_back().map(|(i, c)| (*start + i, c));
+            };
+            let start = end - slice.len();
+            self.end = Some(start);
+            self.back = Some((start, slice.char_indices()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tracked::{Counts, Tracked};
+
+    #[test]
+    fn bytes() {
+        let s = ["a", "b"];
+        let string = MagicString::new(&s);
+        let mut bytes = string.bytes();
+        assert_eq!(bytes.next().unwrap(), b'a');
+        assert_eq!(bytes.next().unwrap(), b'b');
+    }
+
+    #[test]
+    fn chars() {
+        let s = ["a", "b"];
+        let string = MagicString::new(&s);
+        let mut chars = string.chars();
+        assert_eq!(chars.next().unwrap(), 'a');
+        assert_eq!(chars.next().unwrap(), 'b');
+    }
+
+    #[test]
+    fn empty_slices() {
+        // An empty slice isn't the end of the string
+        let s = ["a", "", "", "b", ""];
+        let string = MagicString::new(&s);
+        assert_eq!(string.chars().collect::<String>(), "ab".to_string());
+        assert_eq!(string.bytes().collect::<Vec<_>>(), b"ab".to_vec());
+
+        let s = ["", "a"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.chars().next(), Some('a'));
+        assert_eq!(string.bytes().next(), Some(b'a'));
+    }
+
+    #[test]
+    fn char_indices() {
+        let s = ["a", "🍅", "b"];
+        let string = MagicString::new(&s);
+        let mut chars = string.char_indices();
+        assert_eq!(chars.next().unwrap(), (0, 'a'));
+        assert_eq!(chars.next().unwrap(), (1, '🍅'));
+        assert_eq!(chars.next().unwrap(), (5, 'b'));
+    }
+
+    #[test]
+    fn char_indices_of_view() {
+        let s = ["012", "é3", "", "4🍅"];
+        let string = MagicString::new(&s);
+        let expected = s.concat();
+        for start in (0..=expected.len()).filter(|&i| expected.is_char_boundary(i)) {
+            // Start, end and both offsets, indices are relative to the view
+            let view = string.get(start..);
+            assert!(view.char_indices().eq(expected[start..].char_indices()), "{start}..");
+            assert!(view.char_indices().rev().eq(expected[start..].char_indices().rev()));
+            let end = expected.len() - 4;
+            if start <= end {
+                let view = string.get(start..).get(..end - start);
+                let expected = expected[start..end].char_indices();
+                assert!(view.char_indices().eq(expected), "{start}..{end}");
+            }
+        }
+
+        let view = string.get(2..);
+        assert_eq!(view.char_indices().next(), Some((0, '2')));
+        let view = string.get(2..6);
+        let mut indices = view.char_indices();
+        assert_eq!(indices.next(), Some((0, '2')));
+        assert_eq!(indices.next_back(), Some((3, '3')));
+        assert_eq!(indices.next_back(), Some((1, 'é')));
+        assert_eq!(indices.next(), None);
+    }
+
+    #[test]
+    fn rchars() {
+        let inputs: [&[&str]; 4] = [
+            &["ab", "", "🍅c", "é"],
+            &["", "xyz", ""],
+            &["🍅"],
+            &[],
+        ];
+        for input in inputs {
+            let string = MagicString::new(input);
+            let expected = input.concat();
+            assert_eq!(string.rchars().collect::<String>(), expected.chars().rev().collect::<String>());
+            assert!(string.rev_char_indices().eq(expected.char_indices().rev()));
+        }
+
+        let s = ["012", "345", "678"];
+        let string = MagicString::new(&s).get(1..8);
+        assert_eq!(string.rchars().collect::<String>(), "7654321".to_string());
+        assert!(string.rev_char_indices().eq("1234567".char_indices().rev()));
+    }
+
+    #[test]
+    fn double_ended() {
+        let s = ["ab", "", "🍅c", "é"];
+        let string = MagicString::new(&s);
+
+        let mut iter = string.iter();
+        assert_eq!(iter.next(), Some("ab"));
+        assert_eq!(iter.next_back(), Some("é"));
+        assert_eq!(iter.next(), Some(""));
+        assert_eq!(iter.next_back(), Some("🍅c"));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        let mut chars = string.chars();
+        assert_eq!(chars.next(), Some('a'));
+        assert_eq!(chars.next_back(), Some('é'));
+        assert_eq!(chars.next_back(), Some('c'));
+        assert_eq!(chars.next(), Some('b'));
+        assert_eq!(chars.size_hint(), (1, Some(4)));
+        assert_eq!(chars.next(), Some('🍅'));
+        assert_eq!(chars.next_back(), None);
+        assert_eq!(chars.next(), None);
+
+        // Both ends in the same slice
+        let s = ["abc"];
+        let mut chars = MagicString::new(&s).chars();
+        assert_eq!(chars.next_back(), Some('c'));
+        assert_eq!(chars.next(), Some('a'));
+        assert_eq!(chars.next_back(), Some('b'));
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn char_indices_rev() {
+        let inputs: [&[&str]; 4] = [&["a🍅", "", "bc", "é"], &["", "xy", ""], &["🍅"], &[]];
+        for input in inputs {
+            let string = MagicString::new(input);
+            let expected = input.concat();
+            assert!(string.char_indices().rev().eq(expected.char_indices().rev()));
+        }
+
+        let s = ["012", "345", "678"];
+        let string = MagicString::new(&s).get(1..8);
+        assert!(string.char_indices().rev().eq("1234567".char_indices().rev()));
+
+        // Interleaved from both ends
+        let s = ["a🍅", "", "b", "cé"];
+        let mut indices = MagicString::new(&s).char_indices();
+        assert_eq!(indices.next_back(), Some((7, 'é')));
+        assert_eq!(indices.next(), Some((0, 'a')));
+        assert_eq!(indices.next_back(), Some((6, 'c')));
+        assert_eq!(indices.next_back(), Some((5, 'b')));
+        assert_eq!(indices.next(), Some((1, '🍅')));
+        assert_eq!(indices.next(), None);
+        assert_eq!(indices.next_back(), None);
+    }
+
+    #[test]
+    fn count() {
+        let s = ["aé", "", "🍅", "bc", "", "d"];
+        let string = MagicString::new(&s).get(1..);
+
+        let mut chars = string.chars();
+        let mut bytes = string.bytes();
+        loop {
+            assert_eq!(chars.clone().count(), chars.clone().fold(0, |n, _| n + 1));
+            assert_eq!(bytes.clone().count(), bytes.clone().fold(0, |n, _| n + 1));
+            if chars.next().is_none() {
+                break;
+            }
+            // Also consume from the back, so both ends are partially walked
+            chars.next_back();
+            bytes.next();
+        }
+        assert_eq!(string.chars().count(), 5);
+        assert_eq!(string.bytes().count(), string.len());
+        assert_eq!(MagicString::default().chars().count(), 0);
+    }
+
+    #[test]
+    fn nth() {
+        let s = ["aé", "", "🍅", "bc", "", "d"];
+        let string = MagicString::new(&s).get(1..);
+        let expected = string.to_string();
+
+        for n in 0..12 {
+            assert_eq!(string.bytes().nth(n), expected.as_bytes().get(n).copied(), "{n}");
+            assert_eq!(string.chars().nth(n), expected.chars().nth(n), "{n}");
+        }
+
+        // Skipping keeps the iterators usable, also after reaching the end
+        let mut bytes = string.bytes();
+        let mut expected_bytes = expected.bytes();
+        let mut chars = string.chars();
+        let mut expected_chars = expected.chars();
+        chars.next_back();
+        expected_chars.next_back();
+        for n in [1, 0, 3, 100, 0] {
+            assert_eq!(bytes.nth(n), expected_bytes.nth(n), "{n}");
+            assert_eq!(bytes.next(), expected_bytes.next());
+            assert_eq!(chars.nth(n), expected_chars.nth(n), "{n}");
+            assert_eq!(chars.next(), expected_chars.next());
+        }
+    }
+
+    #[test]
+    fn chars_last() {
+        let s = ["aé", "", "🍅", "bc", ""];
+        let string = MagicString::new(&s);
+        assert_eq!(string.chars().last(), Some('c'));
+        assert_eq!(string.get(..7).chars().last(), Some('🍅'));
+        assert_eq!(string.get(..1).chars().last(), Some('a'));
+        assert_eq!(MagicString::default().chars().last(), None);
+
+        // Only what is left of a partially walked iterator
+        let mut chars = string.chars();
+        chars.next_back();
+        assert_eq!(chars.clone().last(), Some('b'));
+        chars.nth(2);
+        assert_eq!(chars.clone().last(), Some('b'));
+        chars.next();
+        assert_eq!(chars.last(), None);
+    }
+
+    #[test]
+    fn chars_size_hint() {
+        let s = ["aé", "", "🍅", "bc", ""];
+        let string = MagicString::new(&s).get(1..);
+
+        let mut chars = string.chars();
+        let mut char_indices = string.char_indices();
+        let mut count = string.to_string().chars().count();
+        loop {
+            let (lower, upper) = chars.size_hint();
+            assert!(lower <= count && count <= upper.unwrap());
+            assert_eq!(char_indices.size_hint(), (lower, upper));
+            if chars.next().is_none() {
+                break;
+            }
+            char_indices.next();
+            count -= 1;
+        }
+        assert_eq!(count, 0);
+        assert_eq!(chars.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn clone_iterators() {
+        let s = ["ab", "", "🍅c"];
+        let string = MagicString::new(&s);
+
+        let mut chars = string.chars();
+        chars.next();
+        let mut fork = chars.clone();
+        assert_eq!(fork.next(), Some('b'));
+        assert_eq!(fork.next(), Some('🍅'));
+        assert_eq!(chars.collect::<String>(), "b🍅c".to_string());
+        assert_eq!(fork.collect::<String>(), "c".to_string());
+
+        let mut bytes = string.bytes();
+        bytes.next();
+        assert_eq!(bytes.clone().count(), bytes.count());
+
+        let mut indices = string.char_indices();
+        indices.next();
+        let fork = indices.clone();
+        assert!(indices.eq(fork));
+
+        let mut iter = string.iter();
+        iter.next();
+        assert_eq!(iter.clone().collect::<Vec<_>>(), vec!["", "🍅c"]);
+        assert!(!format!("{iter:?}").is_empty());
+    }
+
+    #[test]
+    fn collect() {
+        let s = ["a", "b"];
+        let string = MagicString::new(&s);
+        let chars = string.chars();
+        let actual = chars.collect::<String>();
+        let expected = "ab".to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn split() {
+        let s = ["0123", "4", "56"];
+        let string = MagicString::new(&s);
+        let (left, right) = string.split_at(3);
+
+        let actual = left.iter().collect::<String>();
+        let expected = "012".to_string();
+        assert_eq!(expected, actual);
+
+        let actual = right.iter().collect::<String>();
+        let expected = "3456".to_string();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn split_left() {
+        let s = ["0"];
+        let string = MagicString::new(&s);
+        let (left, _) = string.split_at(1);
+        assert_eq!(left.iter().collect::<String>(), "0".to_string());
+    }
+
+    #[test]
+    fn split_right() {
+        let s = ["0"];
+        let string = MagicString::new(&s);
+        let (_, right) = string.split_at(0);
+        assert_eq!(right.iter().collect::<String>(), "0".to_string());
+    }
+
+    #[test]
+    fn split_empty_sides() {
+        let s = ["01", "23"];
+        let string = MagicString::new(&s);
+
+        let (left, right) = string.split_at(string.len());
+        assert_eq!(left.to_string(), "0123".to_string());
+        assert!(right.is_empty());
+        assert!(right.backing.is_empty());
+        assert!(matches!(right.offset, Offset::None));
+        assert_eq!(right.iter().count(), 0);
+
+        let (left, right) = string.get(1..3).split_at(0);
+        assert_eq!(right.to_string(), "12".to_string());
+        assert!(left.backing.is_empty());
+        assert!(matches!(left.offset, Offset::None));
+
+        let s: [&str; 0] = [];
+        let (left, right) = MagicString::new(&s).split_at(0);
+        assert!(left.is_empty() && right.is_empty());
+    }
+
+    #[test]
+    fn segments() {
+        let s = ["ab", "", "🍅", "cde"];
+        let string = MagicString::new(&s);
+        let actual = string.segments().collect::<Vec<_>>();
+        assert_eq!(actual, vec![(0, "ab"), (2, ""), (2, "🍅"), (6, "cde")]);
+
+        let sub = string.get(1..8);
+        let actual = sub.segments().collect::<Vec<_>>();
+        assert_eq!(actual, vec![(0, "b"), (1, ""), (1, "🍅"), (5, "cd")]);
+        for (offset, slice) in sub.segments() {
+            assert_eq!(sub.get(offset..offset + slice.len()).to_string(), slice);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn to_string() {
+        let s = ["ab", "", "c🍅", "d"];
+        let string = MagicString::new(&s);
+        for view in [string, string.get(1..7), string.get(2..2)] {
+            let actual = view.to_string();
+            assert_eq!(actual, format!("{view}"));
+            // Nothing more than the string itself was reserved
+            assert_eq!(actual.capacity(), view.len());
+        }
+    }
+
+    #[test]
+    fn extend_to_slice() {
+        let s = ["ab", "", "cd", "ef"];
+        // A consumer that is only shown the first slice to begin with
+        let string = MagicString::new(&s).get(..2);
+        assert_eq!(string.to_string(), "ab");
+        let string = string.extend_to_slice(1).unwrap();
+        assert_eq!(string.to_string(), "ab");
+        let string = string.extend_to_slice(1).unwrap();
+        assert_eq!(string.to_string(), "abcd");
+        assert!(string.extend_to_slice(2).is_none());
+        assert!(string.extend_to_slice(usize::MAX).is_none());
+        let string = string.extend_to_slice(1).unwrap();
+        assert_eq!(string.to_string(), "abcdef");
+        assert_eq!(string.len(), 6);
+        assert!(string.extend_to_slice(1).is_none());
+        assert_eq!(string.extend_to_slice(0), Some(string));
+
+        // Views grow from where they start, getting back the end of their last slice
+        let whole = MagicString::new(&s);
+        let view = whole.get(1..3);
+        assert_eq!(view.extend_to_slice(0).unwrap().to_string(), "bcd");
+        assert_eq!(view.extend_to_slice(1).unwrap().to_string(), "bcdef");
+
+        let mut sums = [0; 5];
+        let cached = MagicString::with_prefix_sums(&s, &mut sums).get(1..3);
+        assert_eq!(cached.extend_to_slice(1).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn num_slices() {
+        let s = ["ab", "", "cd", "ef"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.num_slices(), 3);
+        // Leading and trailing slices dropped
+        assert_eq!(string.get(2..4).num_slices(), 1);
+        assert_eq!(string.get(1..5).num_slices(), 3);
+        assert_eq!(string.get(3..3).num_slices(), 0);
+        assert_eq!(MagicString::default().num_slices(), 0);
+        assert_eq!(MagicString::new(&["", ""]).num_slices(), 0);
+    }
+
+    #[test]
+    fn hash() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash(value: impl Hash) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let text = "abcdefghijklmnopqrstuvwxyz".repeat(5);
+        let whole = [text.as_str()];
+        let parts = [&text[..10], "", &text[10..70], &text[70..]];
+        let whole = MagicString::new(&whole);
+        let parts = MagicString::new(&parts);
+        assert_eq!(whole, parts);
+        assert_eq!(hash(whole), hash(parts));
+        assert_ne!(hash(whole), hash(whole.get(..100)));
+
+        // Slices are copied into the blocks whole, so try every place to split them
+        for index in 0..=text.len() {
+            let halves = [&text[..index], &text[index..]];
+            assert_eq!(hash(whole), hash(MagicString::new(&halves)), "{index}");
+        }
+    }
+
+    #[test]
+    fn split_sub_view() {
+        // Splitting a view that already has offsets on both ends
+        let s = ["ab", "c", "", "déf"];
+        let string = MagicString::new(&s);
+        let expected = s.concat();
+        let boundaries = (0..=expected.len()).filter(|&i| expected.is_char_boundary(i));
+        for start in boundaries.clone() {
+            for end in boundaries.clone().filter(|&end| end >= start) {
+                let sub = &expected[start..end];
+                for index in (0..=sub.len()).filter(|&i| sub.is_char_boundary(i)) {
+                    let (left, right) = string.get(start..end).split_at(index);
+                    assert_eq!(left.to_string(), sub[..index].to_string());
+                    assert_eq!(right.to_string(), sub[index..].to_string());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn split_on_boundaries() {
+        let s = ["", "ab", "", "", "cd", "e", ""];
+        let string = MagicString::new(&s);
+        let expected = s.concat();
+        let no_empty_ends = |half: MagicString| {
+            let first = half.iter().next().is_none_or(|s| !s.is_empty());
+            first && half.iter().next_back().is_none_or(|s| !s.is_empty())
+        };
+        for start in 0..=expected.len() {
+            for index in 0..=expected.len() - start {
+                let (left, right) = string.get(start..).split_at(index);
+                assert_eq!(left.to_string(), expected[start..start + index]);
+                assert_eq!(right.to_string(), expected[start + index..]);
+                assert!(no_empty_ends(left), "{start}, {index}: {:?}", left.debug_structure());
+                assert!(no_empty_ends(right), "{start}, {index}: {:?}", right.debug_structure());
+            }
+        }
+
+        // Right at the end of "ab", the empty slices in between go with neither half
+        let (left, right) = string.split_at(2);
+        assert!(left.iter().eq(["ab"]));
+        assert!(right.iter().eq(["cd", "e"]));
+        assert_eq!(left.as_str(), Some("ab"));
+        assert_eq!(right.num_slices(), 2);
+
+        let (left, right) = string.split_at(4);
+        assert!(left.iter().eq(["ab", "", "", "cd"]));
+        assert!(right.iter().eq(["e"]));
+        assert_eq!(right.as_str(), Some("e"));
+        assert_eq!(string.split_at(0).1.iter().next(), Some("ab"));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "not a char boundary")]
+    fn split_inside_char() {
+        let s = ["a", "🍅"];
+        let string = MagicString::new(&s);
+        let _ = string.split_at(3);
+    }
+
+    #[test]
+    fn try_concat() {
+        let s = ["012", "34", "5"];
+        let string = MagicString::new(&s);
+        for index in 0..=string.len() {
+            let (left, right) = string.split_at(index);
+            let joined = left.try_concat(&right).unwrap();
+            assert_eq!(joined.to_string(), "012345".to_string());
+        }
+
+        // Pieces of a sub view
+        let sub = string.get(1..5);
+        let joined = sub.get(..1).try_concat(&sub.get(1..)).unwrap();
+        assert_eq!(joined.to_string(), "1234".to_string());
+        let joined = string.get(..2).try_concat(&string.get(2..4)).unwrap();
+        assert_eq!(joined.to_string(), "0123".to_string());
+
+        // Not next to each other
+        assert!(string.get(..2).try_concat(&string.get(3..)).is_none());
+        assert!(string.get(3..).try_concat(&string.get(..3)).is_none());
+
+        // Different backing slices
+        let other = ["012", "34", "5"];
+        let other = MagicString::new(&other);
+        assert!(string.get(..3).try_concat(&other.get(3..)).is_none());
+    }
+
+    #[test]
+    fn prefix_sums() {
+        let slices = (0..1000)
+            .map(|i| ["", "a", "bc", "🍅", "def", "é"][i % 6])
+            .collect::<Vec<_>>();
+        let mut sums = vec![0; slices.len() + 1];
+        let cached = MagicString::with_prefix_sums(&slices, &mut sums);
+        let linear = MagicString::new(&slices);
+        let len = linear.len();
+        assert_eq!(cached.len(), len);
+
+        for index in (0..=len).filter(|&i| linear.is_char_boundary(i)).step_by(7) {
+            assert_eq!(cached.index(index), linear.index(index));
+            let (cached_left, cached_right) = cached.split_at(index);
+            let (linear_left, linear_right) = linear.split_at(index);
+            assert_eq!(cached_left.len(), linear_left.len());
+            assert_eq!(cached_right.len(), linear_right.len());
+            assert_eq!(cached_left, linear_left);
+            assert_eq!(cached_right, linear_right);
+
+            // Sub views keep using the sums
+            let end = (index + 100).min(len);
+            if linear.is_char_boundary(end) {
+                let cached_sub = cached.get(index..end);
+                let linear_sub = linear.get(index..end);
+                assert_eq!(cached_sub.len(), linear_sub.len());
+                assert_eq!(cached_sub, linear_sub);
+                let trim = |c: char| c.is_ascii();
+                let cached_trimmed = cached_sub.trim_matches_fn(trim);
+                assert_eq!(cached_trimmed.len(), linear_sub.trim_matches_fn(trim).len());
+            }
+        }
+    }
+
+    #[test]
+    fn trim_start() {
+        let s = ["   ", "  ", "a"];
+        let string = MagicString::new(&s);
+        let actual = format!("{}", string.trim_start());
+        let expected = "a".to_string();
+        assert_eq!(expected, actual);
+
+        let actual = format!("{}", string.trim_start().trim_end());
+        let expected = "a".to_string();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn trim_end() {
+        let s = ["a    ", " ", "  "];
+        let string = MagicString::new(&s);
+        let actual = format!("{}", string.trim_end());
+        let expected = "a".to_string();
+        assert_eq!(expected, actual);
+
+        let actual = format!("{}", string.trim_end().trim_start());
+        let expected = "a".to_string();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn trim() {
+        let s = ["  ", " ", "  a    ", " ", "  "];
+        let string = MagicString::new(&s);
+        let actual = format!("{}", string.trim());
+        let expected = "a".to_string();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn trim_with_empty_slices() {
+        let inputs: [&[&str]; 8] = [
+            &["", "  ", "", "a", ""],
+            &["", "", "a b", "", " ", ""],
+            &["", " ", "", " ", ""],
+            &[" a", "", " ", "b ", ""],
+            &["", ""],
+            &[],
+            &["a"],
+            &["\t", "", "\u{3000}ç\u{3000}", "", "\n"],
+        ];
+        for input in inputs {
+            let string = MagicString::new(input);
+            let expected = input.concat();
+            assert_eq!(string.trim_start().to_string(), expected.trim_start(), "{input:?}");
+            assert_eq!(string.trim_end().to_string(), expected.trim_end(), "{input:?}");
+            assert_eq!(string.trim().to_string(), expected.trim(), "{input:?}");
+
+            // Also on views that start and end part way into a slice
+            for start in 0..=expected.len() {
+                for end in start..=expected.len() {
+                    if !expected.is_char_boundary(start) || !expected.is_char_boundary(end) {
+                        continue;
+                    }
+                    let view = string.get(start..end);
+                    let expected = &expected[start..end];
+                    assert_eq!(view.trim_start().to_string(), expected.trim_start());
+                    assert_eq!(view.trim_end().to_string(), expected.trim_end());
+                    assert_eq!(view.trim().to_string(), expected.trim());
+                    assert_eq!(view.trim().len(), expected.trim().len());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn trim_newline() {
+        let inputs: [(&[&str], &str); 9] = [
+            (&["\n"], ""),
+            (&["\r\n"], ""),
+            (&["a\r", "\n", ""], "a"),
+            (&["a", "", "\r\n"], "a"),
+            (&["a\n\n"], "a\n"),
+            (&["a \t", "\n"], "a \t"),
+            (&["a\r"], "a\r"),
+            (&["\na"], "\na"),
+            (&[], ""),
+        ];
+        for (input, expected) in inputs {
+            let string = MagicString::new(input);
+            let trimmed = string.trim_newline();
+            assert_eq!(trimmed.to_string(), expected, "{input:?}");
+            assert_eq!(trimmed.len(), expected.len());
+        }
+
+        let s = ["ab\r\n", "c\n"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.get(1..4).trim_newline().to_string(), "b");
+        assert_eq!(string.get(..4).trim_newline().get(1..).to_string(), "b");
+    }
+
+    #[test]
+    fn trim_matches_fn() {
+        let s = ["!!ab?", "?cd!!"];
+        let string = MagicString::new(&s);
+        let actual = string.trim_matches_fn(|c| !c.is_alphanumeric()).to_string();
+        let expected = "ab??cd".to_string();
+        assert_eq!(expected, actual);
+
+        let s = ["¡¡", "¿a", "bç¿", "¡"];
+        let string = MagicString::new(&s);
+        let actual = string.trim_matches_fn(|c| !c.is_ascii()).to_string();
+        let expected = "ab".to_string();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn split_twice() {
+        let s = ["012345"];
+        let string = MagicString::new(&s);
+        let (_, right) = string.split_at(3);
+        let (left, _) = right.split_at(2);
+        let actual = format!("{}", left);
+        let expected = "34".to_string();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn iter_backwards() {
+        let s = ["0", "1", "2"];
+        let string = MagicString::new(&s);
+
+        let mut iter = string.iter();
+
+        assert_eq!(iter.next_back().unwrap(), "2");
+        assert_eq!(iter.next_back().unwrap(), "1");
+        assert_eq!(iter.next_back().unwrap(), "0");
+    }
+
+    #[test]
+    fn len() {
+        let s = ["12", "3"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.len(), 3);
+
+        let s = [""];
+        let string = MagicString::new(&s);
+        assert_eq!(string.len(), 0);
+
+        let s = [];
+        let string = MagicString::new(&s);
+        assert_eq!(string.len(), 0);
+    }
+
+    #[test]
+    fn pop() {
+        let s = ["0", "1", "2"];
+        let mut string = MagicString::new(&s);
+        let actual = string.pop().unwrap();
+        let expected = '2';
+        assert_eq!(expected, actual);
+
+        let inputs: [&[&str]; 4] = [
+            &["aé", "", "🍅b", "c", ""],
+            &["", "", ""],
+            &["ü"],
+            &[],
+        ];
+        for input in inputs {
+            let expected = input.concat();
+            let mut string = MagicString::new(input);
+            let mut popped = Vec::new();
+            while let Some(c) = string.pop() {
+                popped.push(c);
+                assert_eq!(string.len(), expected.len() - popped.iter().map(|c| c.len_utf8()).sum::<usize>());
+            }
+            assert!(string.is_empty());
+            assert!(popped.into_iter().eq(expected.chars().rev()));
+        }
+
+        let s = ["012", "345", "678"];
+        let mut string = MagicString::new(&s).get(1..8);
+        assert_eq!(string.pop(), Some('7'));
+        assert_eq!(string.to_string(), "123456".to_string());
+    }
+
+    #[test]
+    fn pop_until_empty() {
+        let s = ["", "ab", "", "🍅", "cdé", "", "f"];
+        let mut sums = [0; 8];
+        let strings = [
+            MagicString::new(&s),
+            MagicString::new(&s).get(1..10),
+            MagicString::with_prefix_sums(&s, &mut sums).get(2..),
+        ];
+        for mut string in strings {
+            let mut expected = string.to_string();
+            while let Some(c) = string.pop() {
+                assert_eq!(Some(c), expected.pop());
+                assert_eq!(string.to_string(), expected);
+                assert_eq!(string.len(), expected.len());
+            }
+            assert!(expected.is_empty());
+        }
+    }
+
+    #[test]
+    fn iter_len() {
+        let s = ["01", "", "23", "4", "56"];
+        let string = MagicString::new(&s).get(1..6);
+        let mut iter = string.iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next(), Some("1"));
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next_back(), Some("5"));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(""));
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(iter.by_ref().count(), 2);
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.len(), 0);
+
+        let slices: Vec<&str> = string.iter().collect();
+        assert_eq!(slices.len(), string.iter().len());
+        assert_eq!(MagicString::default().iter().len(), 0);
+    }
+
+    #[test]
+    fn into_iter() {
+        let s = ["012", "34", "567"];
+        let string = MagicString::new(&s).get(1..7);
+
+        let mut actual = Vec::new();
+        for slice in string {
+            actual.push(slice);
+        }
+        assert_eq!(actual, vec!["12", "34", "56"]);
+
+        let mut actual = Vec::new();
+        for slice in &string {
+            actual.push(slice);
+        }
+        assert_eq!(actual, string.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn default() {
+        let string = MagicString::default();
+        assert_eq!(string.len(), 0);
+        assert!(string.is_empty());
+        assert!(string.chars().next().is_none());
+        assert!(string.iter().next().is_none());
+    }
+
+    #[test]
+    fn from() {
+        let s = ["ab", "c"];
+        let string: MagicString = (&s).into();
+        assert_eq!(string.to_string(), "abc".to_string());
+
+        let string: MagicString = s.as_slice().into();
+        assert_eq!(string.to_string(), "abc".to_string());
+
+        let single = "abc";
+        let string: MagicString = (&single).into();
+        assert_eq!(string.to_string(), "abc".to_string());
+        assert_eq!(string.iter().count(), 1);
+    }
+
+    #[test]
+    fn eq() {
+        let a = ["ab", "c"];
+        let b = ["a", "", "bc"];
+        let c = ["abd"];
+        assert_eq!(MagicString::new(&a), MagicString::new(&b));
+        assert_ne!(MagicString::new(&a), MagicString::new(&c));
+        assert_ne!(MagicString::new(&a), MagicString::new(&a).get(..2));
+    }
+
+    #[test]
+    fn sort() {
+        let s1 = ["b", "anana"];
+        let s2 = ["app", "le"];
+        let s3 = ["a"];
+        let s4 = ["", "cherry"];
+        let s5 = ["apple", "s"];
+        let s6 = ["Zebra"];
+        let mut actual = [&s1[..], &s2, &s3, &s4, &s5, &s6]
+            .into_iter()
+            .map(MagicString::new)
+            .collect::<Vec<_>>();
+        actual.sort();
+        let actual = actual.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        let mut expected = vec!["banana", "apple", "a", "cherry", "apples", "Zebra"];
+        expected.sort();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn starts_ends_with_long_pattern() {
+        let counts = Counts::default();
+        let s = ["ab", "c"];
+        let string = Tracked::new(MagicString::new(&s), &counts);
+        let long = "abc".repeat(100);
+        assert!(!string.starts_with(long.as_str()));
+        assert!(!string.ends_with(long.as_str()));
+        assert!(!string.starts_with(MagicString::new(&[long.as_str()])));
+        assert!(!string.ends_with(MagicString::new(&[long.as_str()])));
+        assert_eq!(counts.steps.get(), 0);
+
+        assert!(string.starts_with("ab"));
+        assert!(string.ends_with("bc"));
+        assert!(string.ends_with("abc"));
+        assert!(counts.steps.get() > 0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn try_to_string() {
+        let s = ["ab", "c"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.try_to_string().unwrap(), "abc".to_string());
+
+        let counts = Counts::default();
+        let huge = Tracked::new(string, &counts).with_len(usize::MAX);
+        assert!(huge.try_to_string().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn change_case() {
+        let inputs: [&[&str]; 5] = [
+            &["Hello ", "WORLD"],
+            &["ß", "tRaße"],
+            &["İ", "stanbul"],
+            &["ΟΔΟ", "Σ ΣΑ", "Σ"],
+            &["", "ǅ", "ﬁ"],
+        ];
+        for input in inputs {
+            let string = MagicString::new(input);
+            let expected = input.concat();
+            assert_eq!(expected.to_lowercase(), string.to_lowercase());
+            assert_eq!(expected.to_uppercase(), string.to_uppercase());
+        }
     }
-}
 
-// -----------------------------------------------------------------------------
-//     - Bytes -
-// -----------------------------------------------------------------------------
-/// An iterator over the bytes of the [`MagicString`]
-pub struct Bytes<'a> {
-    inner: MagicIter<'a>,
-    current: Option<StdBytes<'a>>,
-}
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn to_lowercase_final_sigma() {
+        // Case-ignorable chars are looked past on both sides of the 'Σ'
+        let inputs = [
+            "Α'Σ",
+            "Α\u{301}Σ",
+            "ΑΣ\u{301}Β",
+            "ΑΣ'",
+            "ΑΣ\u{301}",
+            "ΑΣ'Β",
+            "'Σ",
+            "Α.:Σ ΣΑ",
+            "ʰΣ",
+            "ΑʰΣ",
+            "1Σ",
+            "Α1Σ",
+            "ǅΣ\u{ad}",
+            "AΣ",
+            "Σ",
+            // A 'Σ' decides whether the one before it is final
+            "ΑΣΣ",
+            "ΑΣ'Σ'",
+            "ΑΣ\u{301}Σ\u{301} ΣΣ.",
+        ];
+        for input in inputs {
+            let expected = input.to_lowercase();
+            assert_eq!(MagicString::new(&[input]).to_lowercase(), expected, "{input:?}");
+            // Split up around the 'Σ'
+            for (index, _) in input.char_indices().skip(1) {
+                let parts = [&input[..index], "", &input[index..]];
+                assert_eq!(MagicString::new(&parts).to_lowercase(), expected, "{parts:?}");
+            }
+        }
 
-impl<'a> Bytes<'a> {
-    fn new(mut inner: MagicIter<'a>) -> Self {
-        let current = inner.next().map(|s| s.bytes());
-        Self { inner, current }
+        // Every ASCII char on either side
+        for c in (0..128u8).map(char::from) {
+            for input in [format!("Α{c}Σ"), format!("{c}Σ"), format!("ΑΣ{c}")] {
+                let expected = input.to_lowercase();
+                let input = [input.as_str()];
+                assert_eq!(MagicString::new(&input).to_lowercase(), expected, "{input:?}");
+            }
+        }
     }
-}
 
-impl<'a> Iterator for Bytes<'a> {
-    type Item = u8;
+    #[test]
+    fn is_contiguous() {
+        let s = ["0123"];
+        let string = MagicString::new(&s);
+        assert!(string.is_contiguous());
+        assert!(string.get(1..3).is_contiguous());
 
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.current.as_mut()?.next() {
-            Some(s) => Some(s),
-            None => {
-                let slice = self.inner.next()?;
-                self.current = Some(slice.bytes());
-                self.current.as_mut()?.next()
-            }
+        let s = ["", "01", "", "23", ""];
+        let string = MagicString::new(&s);
+        assert!(!string.is_contiguous());
+        assert!(!string.get(1..3).is_contiguous());
+        assert!(string.get(..2).is_contiguous());
+        assert!(string.get(2..).is_contiguous());
+        assert!(string.get(2..2).is_contiguous());
+        assert!(MagicString::default().is_contiguous());
+        assert!(MagicString::new(&["", ""]).is_contiguous());
+
+        for end in 0..=string.len() {
+            let view = string.get(..end);
+            assert_eq!(view.is_contiguous(), view.as_str().is_some());
         }
     }
-}
 
-// -----------------------------------------------------------------------------
-//     - Chars -
-// -----------------------------------------------------------------------------
-/// An iterator over the chars of the [`MagicString`]
-pub struct Chars<'a> {
-    inner: MagicIter<'a>,
-    current: Option<StdChars<'a>>,
-}
+    #[test]
+    fn as_str() {
+        let s = ["0123"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.as_str(), Some("0123"));
+        assert_eq!(string.get(1..3).as_str(), Some("12"));
 
-impl<'a> Chars<'a> {
-    fn new(mut inner: MagicIter<'a>) -> Self {
-        let current = inner.next().map(|s| s.chars());
-        Self { inner, current }
+        let s = ["01", "", "23", "45"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.as_str(), None);
+        assert_eq!(string.get(1..4).as_str(), None);
+        assert_eq!(string.get(..2).as_str(), Some("01"));
+        assert_eq!(string.get(3..4).as_str(), Some("3"));
+        assert_eq!(string.get(2..2).as_str(), Some(""));
+        assert_eq!(MagicString::default().as_str(), Some(""));
     }
-}
 
-impl<'a> Iterator for Chars<'a> {
-    type Item = char;
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn to_cow() {
+        let s = ["0123"];
+        let string = MagicString::new(&s);
+        assert!(matches!(string.to_cow(), Cow::Borrowed("0123")));
+        assert!(matches!(string.get(1..3).to_cow(), Cow::Borrowed("12")));
 
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.current.as_mut()?.next() {
-            Some(s) => Some(s),
-            None => {
-                let slice = self.inner.next()?;
-                self.current = Some(slice.chars());
-                self.current.as_mut()?.next()
-            }
+        let s = ["01", "23"];
+        let string = MagicString::new(&s);
+        assert!(matches!(string.to_cow(), Cow::Owned(s) if s == "0123"));
+        assert!(matches!(string.get(1..3).to_cow(), Cow::Owned(s) if s == "12"));
+        assert!(matches!(string.get(2..).to_cow(), Cow::Borrowed("23")));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn write_to() {
+        let s = ["01", "", "2🍅", "45"];
+        let string = MagicString::new(&s);
+        let mut out = Vec::new();
+        assert_eq!(string.write_to(&mut out).unwrap(), 9);
+        assert_eq!(out, "012🍅45".as_bytes());
+
+        let mut out = b"> ".to_vec();
+        assert_eq!(string.get(1..7).write_to(&mut out).unwrap(), 6);
+        assert_eq!(out, "> 12🍅".as_bytes());
+    }
+
+    #[test]
+    fn for_each_slice() {
+        fn crc32(crc: u32, bytes: &[u8]) -> u32 {
+            let step = |crc: u32, _| (crc >> 1) ^ (0xedb88320 & (crc & 1).wrapping_neg());
+            bytes.iter().fold(crc, |crc, &b| (0..8).fold(crc ^ b as u32, step))
         }
+
+        let s = ["01", "", "2🍅", "45", "", "6789"];
+        let string = MagicString::new(&s).get(1..);
+        let mut chunked = !0;
+        let mut chunks = 0;
+        string.for_each_slice(|bytes| {
+            chunked = crc32(chunked, bytes);
+            chunks += 1;
+        });
+        let per_byte = string.bytes().fold(!0, |crc, b| crc32(crc, &[b]));
+        assert_eq!(!chunked, !per_byte);
+        assert_eq!(!chunked, 0xd9fb0827);
+        assert_eq!(chunks, 4);
+
+        let mut called = false;
+        MagicString::default().for_each_slice(|_| called = true);
+        assert!(!called);
     }
-}
 
-// -----------------------------------------------------------------------------
-//     - Char indices -
-// -----------------------------------------------------------------------------
-/// An iterator over the characters and their index of the [`MagicString`]
-pub struct CharIndices<'a> {
-    inner: MagicIter<'a>,
-    current: Option<(usize, StdCharIndices<'a>)>,
-    offset: usize,
-}
+    #[test]
+    fn write_into() {
+        let s = ["01", "", "2🍅", "45"];
+        let string = MagicString::new(&s);
+        let mut out = String::from("> ");
+        string.write_into(&mut out).unwrap();
+        assert_eq!(out, "> 012🍅45");
 
-impl<'a> CharIndices<'a> {
-    fn new(mut inner: MagicIter<'a>) -> Self {
-        let current = inner.next().map(|s| (s.len(), s.char_indices()));
-        Self { inner, current, offset: 0 }
+        string.get(1..7).write_into(&mut out).unwrap();
+        "!".concat(string.get(..0)).write_into(&mut out).unwrap();
+        assert_eq!(out, "> 012🍅4512🍅!");
     }
-}
 
-impl<'a> Iterator for CharIndices<'a> {
-    type Item = (usize, char);
+    #[test]
+    fn copy_to_slice() {
+        let s = ["01", "", "2🍅", "45"];
+        let string = MagicString::new(&s);
+        let expected = "012🍅45".as_bytes();
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let (len, indices) = self.current.as_mut()?;
+        let mut exact = [0; 9];
+        assert_eq!(string.copy_to_slice(&mut exact), Ok(9));
+        assert_eq!(exact, expected);
 
-        match indices.next() {
-            Some((i, c)) => Some((i + self.offset, c)),
-            None => {
-                self.offset += *len;
-                self.current = self.inner.next().map(|s| (s.len(), s.char_indices()));
-                self.next()
-            }
+        let mut small = [0; 8];
+        assert_eq!(string.copy_to_slice(&mut small), Err(9));
+        assert_eq!(small, [0; 8]);
+
+        let mut large = [b'x'; 12];
+        assert_eq!(string.copy_to_slice(&mut large), Ok(9));
+        assert_eq!(&large[..9], expected);
+        assert_eq!(&large[9..], b"xxx");
+
+        let mut sub = [0; 4];
+        assert_eq!(string.get(3..7).copy_to_slice(&mut sub), Ok(4));
+        assert_eq!(sub, "🍅".as_bytes());
+    }
+
+    #[test]
+    fn byte_and_char_at() {
+        let s = ["a", "", "é🍅", "b"];
+        let string = MagicString::new(&s);
+        let expected = "aé🍅b";
+        for index in 0..expected.len() + 2 {
+            assert_eq!(string.byte(index), expected.as_bytes().get(index).copied());
+            let c = expected.get(index..).and_then(|s| s.chars().next());
+            assert_eq!(string.char_at(index), c);
         }
+
+        // In the middle of '🍅'
+        assert_eq!(string.char_at(5), None);
+        assert_eq!(string.byte(5), Some(0x8d));
+
+        let sub = string.get(1..7);
+        assert_eq!(sub.char_at(0), Some('é'));
+        assert_eq!(sub.char_at(2), Some('🍅'));
+        assert_eq!(sub.byte(6), None);
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    #[test]
+    fn first_and_last_char() {
+        let s = ["", "ab", "", "c🍅", "d", ""];
+        let string = MagicString::new(&s);
+        assert_eq!(string.first_char(), Some('a'));
+        assert_eq!(string.last_char(), Some('d'));
+
+        // Views that start and end part way into a slice, or skip whole slices
+        assert_eq!(string.get(1..).first_char(), Some('b'));
+        assert_eq!(string.get(..7).last_char(), Some('🍅'));
+        assert_eq!(string.get(2..3).first_char(), Some('c'));
+        assert_eq!(string.get(2..3).last_char(), Some('c'));
+        assert_eq!(string.get(3..7).first_char(), Some('🍅'));
+        assert_eq!(string.get(1..2).last_char(), Some('b'));
+
+        assert_eq!(string.get(2..2).first_char(), None);
+        assert_eq!(string.get(2..2).last_char(), None);
+        assert_eq!(MagicString::new(&["", ""]).last_char(), None);
+    }
 
     #[test]
-    fn bytes() {
-        let s = ["a", "b"];
+    fn split_first_char() {
+        let s = ["", "é🍅", "", "a"];
         let string = MagicString::new(&s);
-        let mut bytes = string.bytes();
-        assert_eq!(bytes.next().unwrap(), b'a');
-        assert_eq!(bytes.next().unwrap(), b'b');
+
+        // Taking the string apart a char at a time
+        let mut rest = string;
+        let mut chars = Vec::new();
+        while let Some((c, tail)) = rest.split_first_char() {
+            assert_eq!(tail.len(), rest.len() - c.len_utf8());
+            chars.push(c);
+            rest = tail;
+        }
+        assert_eq!(chars, vec!['é', '🍅', 'a']);
+        assert!(rest.is_empty());
+
+        let (first, rest) = string.get(2..).split_first_char().unwrap();
+        assert_eq!(first, '🍅');
+        assert_eq!(rest.to_string(), "a");
+        assert_eq!(rest.first_char(), Some('a'));
+        assert_eq!(rest.split_first_char().map(|(_, tail)| tail.is_empty()), Some(true));
+
+        let (first, rest) = string.get(2..6).split_first_char().unwrap();
+        assert_eq!(first, '🍅');
+        assert!(rest.is_empty());
+        assert_eq!(MagicString::default().split_first_char(), None);
     }
 
     #[test]
-    fn chars() {
-        let s = ["a", "b"];
+    fn find_byte() {
+        let s = ["a,", "é", "", ",🍅b"];
         let string = MagicString::new(&s);
-        let mut chars = string.chars();
-        assert_eq!(chars.next().unwrap(), 'a');
-        assert_eq!(chars.next().unwrap(), 'b');
+        let expected = s.concat();
+        for b in [b'a', b',', b'b', b'x'] {
+            assert_eq!(string.find_byte(b), expected.bytes().position(|x| x == b));
+            assert_eq!(string.rfind_byte(b), expected.bytes().rposition(|x| x == b));
+        }
+
+        // The second byte of 'é' is found, even though no char starts there
+        let second = "é".as_bytes()[1];
+        assert_eq!(string.find_byte(second), Some(3));
+        assert_eq!(string.rfind_byte(second), Some(3));
+        assert!(!string.is_char_boundary(3));
+        // And the last byte of the tomato, in a view that drops the 'b'
+        let last = "🍅".as_bytes()[3];
+        assert_eq!(string.get(..9).rfind_byte(last), Some(8));
+        assert_eq!(string.get(4..).find_byte(b','), Some(0));
+        assert_eq!(MagicString::default().rfind_byte(b','), None);
     }
 
     #[test]
-    fn char_indices() {
-        let s = ["a", "🍅", "b"];
+    fn is_char_boundary() {
+        let s = ["é", "🍅", "", "a", "日"];
         let string = MagicString::new(&s);
-        let mut chars = string.char_indices();
-        assert_eq!(chars.next().unwrap(), (0, 'a'));
-        assert_eq!(chars.next().unwrap(), (1, '🍅'));
-        assert_eq!(chars.next().unwrap(), (5, 'b'));
+        let expected = "é🍅a日";
+        for index in 0..expected.len() + 2 {
+            assert_eq!(string.is_char_boundary(index), expected.is_char_boundary(index));
+        }
+
+        let sub = string.get(2..7);
+        assert!(sub.is_char_boundary(0));
+        assert!(!sub.is_char_boundary(1));
+        assert!(sub.is_char_boundary(4));
+        assert!(sub.is_char_boundary(5));
+        assert!(!sub.is_char_boundary(6));
     }
 
     #[test]
-    fn collect() {
-        let s = ["a", "b"];
+    fn count_lines() {
+        let inputs: [&[&str]; 9] = [
+            &["a\nb", "\nc"],
+            &["a\nb", "\nc\n"],
+            &["a\r", "\nb\r\n", "", "\r\n"],
+            &["\n", "", "\n"],
+            &["a\r"],
+            &["", ""],
+            &[],
+            &["no newline"],
+            &["\n", "x"],
+        ];
+        for input in inputs {
+            let string = MagicString::new(input);
+            assert_eq!(string.count_lines(), input.concat().lines().count(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn is_ascii() {
+        let s = ["abc", "", "de", "fé", "g"];
         let string = MagicString::new(&s);
-        let chars = string.chars();
-        let actual = chars.collect::<String>();
-        let expected = "ab".to_string();
-        assert_eq!(actual, expected);
+        assert!(!string.is_ascii());
+        assert!(string.get(..6).is_ascii());
+        assert!(!string.get(6..).is_ascii());
+        assert!(string.get(8..).is_ascii());
+        assert!(MagicString::default().is_ascii());
     }
 
     #[test]
-    fn split() {
-        let s = ["0123", "4", "56"];
+    fn contains_whitespace() {
+        let s = ["ab", "\u{3000}", "c"];
         let string = MagicString::new(&s);
-        let (left, right) = string.split_at(3);
+        assert!(string.contains_whitespace());
+        assert!(!string.get(..2).contains_whitespace());
+        assert!(!string.get(5..).contains_whitespace());
 
-        let actual = left.iter().collect::<String>();
-        let expected = "012".to_string();
-        assert_eq!(expected, actual);
+        // Whitespace at the end of one slice and the start of the next
+        let s = ["ab ", "cd", "\tef"];
+        let string = MagicString::new(&s);
+        assert!(string.get(..3).contains_whitespace());
+        assert!(string.get(3..).contains_whitespace());
+        assert!(!string.get(3..5).contains_whitespace());
+        assert!(!MagicString::default().contains_whitespace());
+    }
 
-        let actual = right.iter().collect::<String>();
-        let expected = "3456".to_string();
-        assert_eq!(expected, actual);
+    #[test]
+    fn peek_char() {
+        let s = ["", "a🍅", "", "b"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.peek_char(), Some('a'));
+        assert_eq!(string.peek_char(), Some('a'));
+        // Peeking doesn't move anything along
+        assert!(string.chars().eq("a🍅b".chars()));
+        assert_eq!(string.peek_char_at(0), Some('a'));
+
+        let expected = s.concat();
+        for index in 0..expected.len() + 2 {
+            let peeked = string.peek_char_at(index);
+            assert_eq!(peeked, expected.get(index..).and_then(|s| s.chars().next()), "{index}");
+        }
+
+        let view = string.get(1..);
+        assert_eq!(view.peek_char(), Some('🍅'));
+        assert_eq!(view.peek_char_at(4), Some('b'));
+        assert_eq!(view.get(4..).peek_char(), Some('b'));
+        assert_eq!(view.get(5..).peek_char(), None);
+        assert_eq!(MagicString::default().peek_char_at(0), None);
     }
 
     #[test]
-    fn split_left() {
-        let s = ["0"];
+    fn chars_eq() {
+        let s = ["", "a", "b🍅", "", "c"];
         let string = MagicString::new(&s);
-        let (left, _) = string.split_at(1);
-        assert_eq!(left.iter().collect::<String>(), "0".to_string());
+        assert!(string.chars_eq("ab🍅c".chars()));
+        assert!(string.get(1..).chars_eq(['b', '🍅', 'c']));
+        assert!(string.get(2..6).chars_eq("🍅".chars()));
+        assert!(!string.chars_eq("ab🍅".chars()));
+        assert!(!string.chars_eq("ab🍅cd".chars()));
+        assert!(!string.chars_eq("ab🍅d".chars()));
+        assert!(string.get(..0).chars_eq(core::iter::empty()));
+        assert!(!MagicString::default().chars_eq(Some('a')));
     }
 
     #[test]
-    fn split_right() {
-        let s = ["0"];
+    fn eq_ignoring_whitespace() {
+        let s = [" a", "b \t", "", "\n🍅 c", "  "];
         let string = MagicString::new(&s);
-        let (_, right) = string.split_at(0);
-        assert_eq!(right.iter().collect::<String>(), "0".to_string());
+        assert!(string.eq_ignoring_whitespace("ab🍅c"));
+        assert!(string.eq_ignoring_whitespace("  a\tb  🍅\r\nc"));
+        assert!(string.eq_ignoring_whitespace("a\u{3000}b🍅c\n"));
+        assert!(!string.eq_ignoring_whitespace("ab🍅"));
+        assert!(!string.eq_ignoring_whitespace("ab🍅cd"));
+        assert!(!string.eq_ignoring_whitespace("a b c"));
+        assert!(string.get(3..).eq_ignoring_whitespace("🍅 c"));
+        assert!(MagicString::default().eq_ignoring_whitespace(" \t"));
+        assert!(!MagicString::default().eq_ignoring_whitespace("a"));
     }
 
     #[test]
-    fn trim_start() {
-        let s = ["   ", "  ", "a"];
+    fn eq_ignore_ascii_case() {
+        let s = ["AbC", "dE"];
         let string = MagicString::new(&s);
-        let actual = format!("{}", string.trim_start());
-        let expected = "a".to_string();
-        assert_eq!(expected, actual);
+        assert!(string.eq_ignore_ascii_case("abcde"));
+        assert!(string.eq_ignore_ascii_case("ABCDE"));
+        assert!(!string.eq_ignore_ascii_case("abcd"));
+        assert!(!string.eq_ignore_ascii_case("abcdef"));
+        assert!(!string.eq_ignore_ascii_case("abxde"));
+
+        let other = ["a", "BCd", "", "e"];
+        let other = MagicString::new(&other);
+        assert!(string.eq_ignore_ascii_case(other));
+        assert!(!string.eq_ignore_ascii_case(other.get(1..)));
+
+        let s = ["Ä", "b"];
+        let string = MagicString::new(&s);
+        assert!(string.eq_ignore_ascii_case("ÄB"));
+        assert!(!string.eq_ignore_ascii_case("äb"));
+    }
 
-        let actual = format!("{}", string.trim_start().trim_end());
-        let expected = "a".to_string();
-        assert_eq!(expected, actual);
+    #[test]
+    fn nth_char() {
+        let s = ["aé", "", "🍅bc", "ß", "日本"];
+        let string = MagicString::new(&s);
+        for n in 0..10 {
+            assert_eq!(string.nth_char(n), string.chars().nth(n));
+        }
+
+        let (_, right) = string.split_at(3);
+        for n in 0..10 {
+            assert_eq!(right.nth_char(n), right.chars().nth(n));
+        }
     }
 
     #[test]
-    fn trim_end() {
-        let s = ["a    ", " ", "  "];
+    #[cfg(feature = "alloc")]
+    fn pad() {
+        let s = ["a", "日本", "", "🍅"];
         let string = MagicString::new(&s);
-        let actual = format!("{}", string.trim_end());
-        let expected = "a".to_string();
-        assert_eq!(expected, actual);
+        let pad_end = |string: MagicString, width, fill| {
+            let mut out = String::from("|");
+            string.pad_end(width, fill, &mut out);
+            out
+        };
+        let pad_start = |string: MagicString, width, fill| {
+            let mut out = String::from("|");
+            string.pad_start(width, fill, &mut out);
+            out
+        };
 
-        let actual = format!("{}", string.trim_end().trim_start());
-        let expected = "a".to_string();
-        assert_eq!(expected, actual);
+        // 11 bytes, but 7 columns wide
+        assert_eq!(pad_end(string, 10, '-'), "|a日本🍅---");
+        assert_eq!(pad_start(string, 10, '-'), "|---a日本🍅");
+        assert_eq!(pad_end(string, 11, '-'), "|a日本🍅----");
+        assert_eq!(pad_end(string, 7, '-'), "|a日本🍅");
+        assert_eq!(pad_start(string, 3, '-'), "|a日本🍅");
+        assert_eq!(pad_end(string.get(1..7), 5, ' '), "|日本 ");
+        assert_eq!(pad_start(MagicString::default(), 2, '.'), "|..");
+
+        // Wide fill chars never go past the width
+        assert_eq!(pad_end(string, 10, '＿'), "|a日本🍅＿");
+        assert_eq!(pad_start(string, 11, '＿'), "|＿＿a日本🍅");
     }
 
     #[test]
-    fn trim() {
-        let s = ["  ", " ", "  a    ", " ", "  "];
+    #[cfg(feature = "alloc")]
+    fn center() {
+        let s = ["a", "日本", ""];
         let string = MagicString::new(&s);
-        let actual = format!("{}", string.trim());
-        let expected = "a".to_string();
-        assert_eq!(expected, actual);
+        let center = |width, fill| {
+            let mut out = String::from("|");
+            string.center(width, fill, &mut out);
+            out
+        };
+
+        // 5 columns wide, padded evenly and unevenly
+        assert_eq!(center(9, '-'), "|--a日本--");
+        assert_eq!(center(8, '-'), "|-a日本--");
+        assert_eq!(center(6, '-'), "|a日本-");
+        assert_eq!(center(5, '-'), "|a日本");
+        assert_eq!(center(2, '-'), "|a日本");
+        assert_eq!(center(9, '＿'), "|＿a日本＿");
     }
 
     #[test]
-    fn split_twice() {
-        let s = ["012345"];
+    fn truncate_to_width() {
+        let s = ["a", "日本", "🍅b"];
         let string = MagicString::new(&s);
-        let (_, right) = string.split_at(3);
-        let (left, _) = right.split_at(2);
-        let actual = format!("{}", left);
-        let expected = "34".to_string();
-        assert_eq!(expected, actual);
+        let expected = ["", "a", "a", "a日", "a日", "a日本", "a日本", "a日本🍅"];
+        for (max, expected) in expected.into_iter().enumerate() {
+            let (prefix, truncated) = string.truncate_to_width(max);
+            assert_eq!(prefix.to_string(), expected, "{max}");
+            assert!(prefix.width() <= max);
+            assert!(truncated);
+        }
+
+        let (prefix, truncated) = string.truncate_to_width(8);
+        assert_eq!(prefix, string);
+        assert!(!truncated);
+
+        // Zero width chars don't count towards the budget
+        let s = ["e\u{301}", "x"];
+        let (prefix, truncated) = MagicString::new(&s).truncate_to_width(1);
+        assert_eq!(prefix.to_string(), "e\u{301}");
+        assert!(truncated);
+
+        // The variation selector makes the heart two columns wide, and isn't cut off it
+        let s = ["\u{2764}\u{FE0F}"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.width(), 2);
+        let (prefix, truncated) = string.truncate_to_width(1);
+        assert_eq!(prefix.to_string(), "");
+        assert!(truncated);
+        assert_eq!(string.truncate_to_width(2), (string, false));
+
+        // Also when the variation selector starts the next slice
+        let s = ["a\u{2764}", "\u{FE0F}b"];
+        let string = MagicString::new(&s);
+        let (prefix, truncated) = string.truncate_to_width(2);
+        assert_eq!(prefix.to_string(), "a");
+        assert!(truncated);
+        let (prefix, truncated) = string.truncate_to_width(3);
+        assert_eq!(prefix.to_string(), "a\u{2764}\u{FE0F}");
+        assert!(truncated);
     }
 
     #[test]
-    fn iter_backwards() {
-        let s = ["0", "1", "2"];
+    fn debug_structure() {
+        let s = ["012", "", "345", "67"];
         let string = MagicString::new(&s);
+        assert_eq!(format!("{:?}", string.debug_structure()), r#"["012", "", "345", "67"]"#);
 
-        let mut iter = string.iter();
+        let sub = string.get(2..7);
+        assert_eq!(format!("{sub:?}"), r#""2""""345""6""#);
+        let structure = format!("{:?}", sub.debug_structure());
+        assert_eq!(structure, r#"["012"+start=2, "", "345", "67"-end=1]"#);
 
-        assert_eq!(iter.next_back().unwrap(), "2");
-        assert_eq!(iter.next_back().unwrap(), "1");
-        assert_eq!(iter.next_back().unwrap(), "0");
+        let sub = string.get(4..5);
+        assert_eq!(format!("{:?}", sub.debug_structure()), r#"["345"+start=1-end=1]"#);
+        assert_eq!(format!("{:?}", MagicString::default().debug_structure()), "[]");
     }
 
     #[test]
-    fn len() {
-        let s = ["12", "3"];
+    fn split_at_checked() {
+        let s = ["aé", "", "🍅b", "ß"];
         let string = MagicString::new(&s);
-        assert_eq!(string.len(), 3);
+        let expected = "aé🍅bß";
+        for index in 0..expected.len() + 2 {
+            let actual = string.split_at_checked(index);
+            let actual = actual.map(|(left, right)| (left.to_string(), right.to_string()));
+            let expected = expected.split_at_checked(index);
+            let expected = expected.map(|(left, right)| (left.to_string(), right.to_string()));
+            assert_eq!(actual, expected, "{index}");
+        }
 
-        let s = [""];
+        // Inside the tomato of a view
+        let view = string.get(1..8);
+        assert_eq!(view.split_at_checked(3), None);
+        let (left, right) = view.split_at_checked(6).unwrap();
+        assert_eq!(left.to_string(), "é🍅");
+        assert_eq!(right.to_string(), "b");
+        assert_eq!(view.split_at_checked(8), None);
+    }
+
+    #[test]
+    fn split_at_char() {
+        let s = ["aé", "", "🍅bc", "ß"];
         let string = MagicString::new(&s);
-        assert_eq!(string.len(), 0);
+        let expected = "aé🍅bcß";
+        for n in 0..=expected.chars().count() {
+            let (left, right) = string.split_at_char(n);
+            let index = expected.char_indices().nth(n).map_or(expected.len(), |(i, _)| i);
+            assert_eq!(left.to_string(), expected[..index]);
+            assert_eq!(right.to_string(), expected[index..]);
+            assert_eq!(left.chars().count(), n);
+        }
 
-        let s = [];
+        let (left, right) = string.split_at_char(100);
+        assert_eq!(left, string);
+        assert!(right.is_empty());
+    }
+
+    #[test]
+    fn rsplit_once() {
+        let inputs: [&[&str]; 6] = [
+            &["a=b", "=c", "", "=d"],
+            &["a=", "=", "b"],
+            &["🍅=", "🍅"],
+            &["abc"],
+            &["", "="],
+            &[],
+        ];
+        fn owned(halves: (impl ToString, impl ToString)) -> (String, String) {
+            (halves.0.to_string(), halves.1.to_string())
+        }
+
+        for input in inputs {
+            let string = MagicString::new(input);
+            let expected = input.concat();
+            for sep in ["=", "==", "a", "x", ""] {
+                let actual = string.rsplit_once(sep).map(owned);
+                assert_eq!(actual, expected.rsplit_once(sep).map(owned), "{input:?} {sep:?}");
+            }
+            let actual = string.rsplit_once('=').map(owned);
+            assert_eq!(actual, expected.rsplit_once('=').map(owned), "{input:?}");
+        }
+
+        // The halves of a view are views themselves
+        let s = ["k1=v", "1;k2", "=v2"];
+        let string = MagicString::new(&s).get(6..);
+        let (head, tail) = string.rsplit_once('=').unwrap();
+        assert_eq!(head.to_string(), "k2");
+        assert_eq!(tail.to_string(), "v2");
+    }
+
+    #[test]
+    fn get_chars() {
+        let s = ["aé", "", "🍅bc", "ß"];
         let string = MagicString::new(&s);
-        assert_eq!(string.len(), 0);
+        let expected = "aé🍅bcß".chars().collect::<Vec<_>>();
+        for start in 0..=expected.len() + 1 {
+            for end in start..=expected.len() + 1 {
+                let clamped = &expected[start.min(expected.len())..end.min(expected.len())];
+                let actual = string.get_chars(start..end).to_string();
+                assert_eq!(actual, clamped.iter().collect::<String>(), "{start}..{end}");
+            }
+        }
+
+        // Char indices don't line up with byte indices once there is a multi-byte char
+        assert_eq!(string.get(1..3).to_string(), "é");
+        assert_eq!(string.get_chars(1..3).to_string(), "é🍅");
+        assert_eq!(string.get_chars(..=1).to_string(), "aé");
+        assert_eq!(string.get_chars(2..).to_string(), "🍅bcß");
+        assert_eq!(string.get_chars(..), string);
+        assert_eq!(string.get(3..).get_chars(1..3).to_string(), "bc");
+        assert!(MagicString::default().get_chars(1..).is_empty());
+
+        // Inclusive ends at `usize::MAX` are clamped too
+        use core::ops::Bound::{Excluded, Included, Unbounded};
+        assert_eq!(string.get_chars(..=usize::MAX), string);
+        assert_eq!(string.get_chars(1..=usize::MAX).to_string(), "é🍅bcß");
+        assert!(string.get_chars((Included(usize::MAX), Unbounded)).is_empty());
+        assert!(string.get_chars((Excluded(usize::MAX), Included(usize::MAX))).is_empty());
     }
 
     #[test]
-    fn pop() {
-        let s = ["0", "1", "2"];
-        let mut string = MagicString::new(&s);
-        let actual = string.pop().unwrap();
-        let expected = '2';
-        assert_eq!(expected, actual);
+    fn find_from() {
+        let s = ["a🍅", "b", "a🍅b"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.find_from(0, 'a'), Some(0));
+        assert_eq!(string.find_from(1, 'a'), Some(6));
+        assert_eq!(string.find_from(6, 'a'), Some(6));
+        assert_eq!(string.find_from(7, 'a'), None);
+
+        // Matches spanning slices, before and after `start`
+        assert_eq!(string.find_from(0, "🍅b"), Some(1));
+        assert_eq!(string.find_from(2, "🍅b"), Some(7));
+        assert_eq!(string.find_from(5, "ba"), Some(5));
+        assert_eq!(string.find_from(6, "ba"), None);
+        assert_eq!(string.find_from(100, "ba"), None);
+
+        assert_eq!(string.find_from(3, ""), Some(3));
+        assert_eq!(string.find_from(string.len(), ""), Some(string.len()));
+        assert_eq!(string.find_from(string.len() + 1, ""), None);
+
+        let view = string.get(5..);
+        assert_eq!(view.find_from(1, 'a'), Some(1));
+        assert_eq!(view.find_from(1, "🍅b"), view.get(1..).find("🍅b").map(|i| i + 1));
+    }
+
+    #[test]
+    fn char_index_to_byte() {
+        let s = ["aé", "", "🍅bc", "ß", "日本"];
+        let string = MagicString::new(&s);
+        let expected = "aé🍅bcß日本";
+        for (n, (index, c)) in expected.char_indices().enumerate() {
+            assert_eq!(string.char_index_to_byte(n), Some(index));
+            assert_eq!(string.nth_char(n), Some(c));
+        }
+
+        let count = expected.chars().count();
+        assert_eq!(string.char_index_to_byte(count), Some(expected.len()));
+        assert_eq!(string.char_index_to_byte(count + 1), None);
+        assert_eq!(string.nth_char(count), None);
+
+        let (_, right) = string.split_at(1);
+        assert_eq!(right.char_index_to_byte(1), Some(2));
+        assert_eq!(right.nth_char(1), Some('🍅'));
     }
 }
@@ -0,0 +1,227 @@
+use core::iter::Map;
+
+use crate::pattern::Pattern;
+use crate::MagicStringTrait;
+
+/// Iterates over the matches of either a [`char`] or a `&str`.
+/// Matches may span several of the inner slices.
+pub trait Matches<P>: Sized {
+    /// An iterator over the non-overlapping matches of the pattern and their positions
+    fn match_indices(&self, pat: P) -> MatchIndices<Self, P>;
+
+    /// An iterator over the non-overlapping matches of the pattern
+    #[allow(clippy::type_complexity)]
+    fn matches(&self, pat: P) -> Map<MatchIndices<Self, P>, fn((usize, Self)) -> Self>;
+
+    /// The number of non-overlapping matches of the pattern
+    fn count(&self, pat: P) -> usize;
+
+    /// An iterator over the non-overlapping matches of the pattern and their positions,
+    /// searching from the end of the string
+    fn rmatch_indices(&self, pat: P) -> RMatchIndices<Self, P>;
+
+    /// An iterator over the non-overlapping matches of the pattern,
+    /// searching from the end of the string
+    #[allow(clippy::type_complexity)]
+    fn rmatches(&self, pat: P) -> Map<RMatchIndices<Self, P>, fn((usize, Self)) -> Self>;
+}
+
+impl<'a, T: MagicStringTrait<'a>, P: Pattern> Matches<P> for T {
+    fn match_indices(&self, pat: P) -> MatchIndices<Self, P> {
+        MatchIndices { rest: *self, pat, consumed: 0, done: false }
+    }
+
+    fn matches(&self, pat: P) -> Map<MatchIndices<Self, P>, fn((usize, Self)) -> Self> {
+        let view: fn((usize, Self)) -> Self = |(_, view)| view;
+        self.match_indices(pat).map(view)
+    }
+
+    fn count(&self, pat: P) -> usize {
+        self.match_indices(pat).count()
+    }
+
+    fn rmatch_indices(&self, pat: P) -> RMatchIndices<Self, P> {
+        RMatchIndices { string: *self, pat, end: self.len(), done: false }
+    }
+
+    fn rmatches(&self, pat: P) -> Map<RMatchIndices<Self, P>, fn((usize, Self)) -> Self> {
+        let view: fn((usize, Self)) -> Self = |(_, view)| view;
+        self.rmatch_indices(pat).map(view)
+    }
+}
+
+/// An iterator over the matches of a pattern and their positions,
+/// see [`Matches::match_indices`]
+#[derive(Clone, Debug)]
+pub struct MatchIndices<T, P> {
+    // What is left to search, starting at byte `consumed` of the original string.
+    // Every search starts where the last one stopped, instead of at the start.
+    rest: T,
+    pat: P,
+    consumed: usize,
+    done: bool,
+}
+
+impl<'a, T: MagicStringTrait<'a>, P: Pattern> Iterator for MatchIndices<T, P> {
+    type Item = (usize, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let Some(start) = self.pat.find_in(&self.rest, 0) else {
+            self.done = true;
+            return None;
+        };
+        let len = self.pat.match_len();
+        let (_, rest) = self.rest.split_at(start);
+        let (found, mut rest) = rest.split_at(len);
+        let position = self.consumed + start;
+        self.consumed = position + len;
+
+        if len == 0 {
+            // An empty pattern matches between every char, so step over the next one
+            match rest.first_char() {
+                Some(c) => {
+                    (_, rest) = rest.split_at(c.len_utf8());
+                    self.consumed += c.len_utf8();
+                }
+                None => self.done = true,
+            }
+        }
+
+        self.rest = rest;
+        Some((position, found))
+    }
+}
+
+/// An iterator over the matches of a pattern and their positions from the end
+/// of the string, see [`Matches::rmatch_indices`]
+#[derive(Clone, Debug)]
+pub struct RMatchIndices<T, P> {
+    string: T,
+    pat: P,
+    end: usize,
+    done: bool,
+}
+
+impl<'a, T: MagicStringTrait<'a>, P: Pattern> Iterator for RMatchIndices<T, P> {
+    type Item = (usize, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let Some(start) = self.pat.rfind_in(&self.string, self.end) else {
+            self.done = true;
+            return None;
+        };
+        let end = start + self.pat.match_len();
+
+        self.end = start;
+        if start == end {
+            // An empty pattern matches between every char, so step back over the previous one
+            match self.string.get(..start).chars().next_back() {
+                Some(c) => self.end -= c.len_utf8(),
+                None => self.done = true,
+            }
+        }
+
+        Some((start, self.string.get(start..end)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MagicString;
+
+    #[test]
+    fn count() {
+        let s = ["abca", "b", "xa", "", "bab"];
+        let string = MagicString::new(&s);
+        let expected = s.concat();
+        assert_eq!(string.count('a'), expected.matches('a').count());
+        assert_eq!(string.count('x'), 1);
+        assert_eq!(string.count('z'), 0);
+        // "ab" appears within a slice and across three different boundaries
+        assert_eq!(string.count("ab"), 4);
+        assert_eq!(string.count("bab"), 1);
+        assert_eq!(string.count(""), expected.matches("").count());
+    }
+
+    #[test]
+    fn match_indices() {
+        let inputs: [&[&str]; 3] = [&["aa", "a", "aa"], &["🍅a", "🍅", "🍅"], &["", "ab"]];
+        for input in inputs {
+            let string = MagicString::new(input);
+            let expected = input.concat();
+            for pat in ["aa", "🍅", "a🍅", ""] {
+                let actual = string
+                    .match_indices(pat)
+                    .map(|(i, m)| (i, m.to_string()))
+                    .collect::<Vec<_>>();
+                let expected = expected
+                    .match_indices(pat)
+                    .map(|(i, m)| (i, m.to_string()))
+                    .collect::<Vec<_>>();
+                assert_eq!(expected, actual, "{pat:?} in {input:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn matches() {
+        let s = ["x1", "2y1", "2"];
+        let string = MagicString::new(&s);
+        let actual = string.matches("12").map(|m| m.iter().count()).collect::<Vec<_>>();
+        // Both matches cross a boundary and are made up of two slices
+        assert_eq!(actual, vec![2, 2]);
+    }
+
+    #[test]
+    fn rmatch_indices() {
+        let inputs: [&[&str]; 4] = [
+            &["aa", "a", "aa"],
+            &["🍅a", "🍅", "🍅"],
+            &["", "ab"],
+            &["a", "b", "ab", "", "a"],
+        ];
+        for input in inputs {
+            let string = MagicString::new(input);
+            let expected = input.concat();
+            for pat in ["aa", "🍅", "a🍅", "ab", "ba", ""] {
+                let actual = string
+                    .rmatch_indices(pat)
+                    .map(|(i, m)| (i, m.to_string()))
+                    .collect::<Vec<_>>();
+                let expected = expected
+                    .rmatch_indices(pat)
+                    .map(|(i, m)| (i, m.to_string()))
+                    .collect::<Vec<_>>();
+                assert_eq!(expected, actual, "{pat:?} in {input:?}");
+            }
+            for pat in ['a', '🍅'] {
+                let actual = string.rmatch_indices(pat).map(|(i, _)| i).collect::<Vec<_>>();
+                let expected = expected.rmatch_indices(pat).map(|(i, _)| i).collect::<Vec<_>>();
+                assert_eq!(expected, actual, "{pat:?} in {input:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn rmatches() {
+        let s = ["x1", "2y1", "2"];
+        let string = MagicString::new(&s);
+        let actual = string.rmatches("12").map(|m| m.to_string()).collect::<Vec<_>>();
+        assert_eq!(actual, vec!["12", "12"]);
+        let actual = string.get(..5).rmatch_indices("12").map(|(i, _)| i).collect::<Vec<_>>();
+        assert_eq!(actual, vec![1]);
+        // Overlapping matches are found from the end, like `str::rmatches`
+        let s = ["a", "a", "a"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.rmatch_indices("aa").map(|(i, _)| i).collect::<Vec<_>>(), vec![1]);
+    }
+}
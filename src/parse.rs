@@ -0,0 +1,103 @@
+use core::fmt;
+use core::str::{self, FromStr};
+
+use crate::MagicStringTrait;
+
+// Strings of several slices up to this many bytes are parsed from a copy on the stack
+const STACK_BUFFER_LEN: usize = 64;
+
+/// The error returned by [`MagicStringTrait::parse`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError<E> {
+    /// The string was parsed, but [`FromStr::from_str`] failed
+    Parse(E),
+    /// The string is made up of several slices and is too long to be copied onto
+    /// the stack. Only returned when the `alloc` feature is disabled.
+    NotContiguous,
+}
+
+impl<E: fmt::Display> fmt::Display for ParseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => err.fmt(f),
+            Self::NotContiguous => write!(f, "string is too long to be parsed without allocating"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for ParseError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Parse(err) => Some(err),
+            Self::NotContiguous => None,
+        }
+    }
+}
+
+pub(crate) fn parse<'a, F: FromStr>(
+    string: &impl MagicStringTrait<'a>,
+) -> Result<F, ParseError<F::Err>> {
+    if let Some(s) = string.as_str() {
+        return s.parse().map_err(ParseError::Parse);
+    }
+
+    let mut buf = [0; STACK_BUFFER_LEN];
+    if let Ok(len) = string.copy_to_slice(&mut buf) {
+        let s = str::from_utf8(&buf[..len]).expect("the slices are all valid UTF-8");
+        return s.parse().map_err(ParseError::Parse);
+    }
+
+    #[cfg(feature = "alloc")]
+    return string.to_cow().parse().map_err(ParseError::Parse);
+    #[cfg(not(feature = "alloc"))]
+    Err(ParseError::NotContiguous)
+}
+
+#[cfg(test)]
+mod test {
+    use core::num::ParseIntError;
+
+    use super::*;
+    use crate::MagicString;
+
+    #[test]
+    fn parse_int() {
+        let s = ["12345"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.parse::<u32>(), Ok(12345));
+        assert_eq!(string.get(1..3).parse::<u8>(), Ok(23));
+
+        let s = ["-1", "", "23", "45"];
+        let string = MagicString::new(&s);
+        assert_eq!(string.parse::<i32>(), Ok(-12345));
+        assert_eq!(string.get(1..).parse::<u64>(), Ok(12345));
+        assert_eq!("".concat("4").concat("2").parse::<u8>(), Ok(42));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let s = ["12", "x"];
+        let string = MagicString::new(&s);
+        let expected = "12x".parse::<u32>().unwrap_err();
+        assert_eq!(string.parse::<u32>(), Err(ParseError::Parse(expected)));
+        assert!(matches!(MagicString::default().parse::<u32>(), Err(ParseError::Parse(_))));
+    }
+
+    #[test]
+    fn parse_long() {
+        let digits = ["1234567890"; 10];
+        let string = MagicString::new(&digits);
+        let result: Result<u128, ParseError<ParseIntError>> = string.parse();
+        #[cfg(feature = "alloc")]
+        assert!(matches!(result, Err(ParseError::Parse(_))));
+        #[cfg(not(feature = "alloc"))]
+        assert_eq!(result, Err(ParseError::NotContiguous));
+
+        // Long strings made up of a single slice never need a copy
+        let digits = digits.concat();
+        let s = [digits.as_str()];
+        let result = MagicString::new(&s).parse::<f64>();
+        assert_eq!(result, Ok(digits.parse::<f64>().unwrap()));
+    }
+}
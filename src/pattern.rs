@@ -0,0 +1,50 @@
+use crate::find::{find_needle, rfind_needle};
+use crate::sealed::Sealed;
+use crate::MagicStringTrait;
+
+/// A pattern that can be searched for across the slices of a magic string,
+/// either a [`char`] or a `&str`.
+///
+/// This trait is sealed and can't be implemented outside of this crate.
+pub trait Pattern: Copy + Sealed {
+    /// The length of a match in bytes
+    fn match_len(&self) -> usize;
+
+    /// The position of the first match at or after `start`
+    fn find_in<'a>(&self, haystack: &impl MagicStringTrait<'a>, start: usize) -> Option<usize>;
+
+    /// The position of the last match that ends at or before `end`
+    fn rfind_in<'a>(&self, haystack: &impl MagicStringTrait<'a>, end: usize) -> Option<usize>;
+}
+
+impl Pattern for char {
+    fn match_len(&self) -> usize {
+        self.len_utf8()
+    }
+
+    fn find_in<'a>(&self, haystack: &impl MagicStringTrait<'a>, start: usize) -> Option<usize> {
+        let mut buf = [0; 4];
+        let needle: &str = self.encode_utf8(&mut buf);
+        find_needle(haystack, &needle, start)
+    }
+
+    fn rfind_in<'a>(&self, haystack: &impl MagicStringTrait<'a>, end: usize) -> Option<usize> {
+        let mut buf = [0; 4];
+        let needle: &str = self.encode_utf8(&mut buf);
+        rfind_needle(haystack, &needle, end)
+    }
+}
+
+impl Pattern for &str {
+    fn match_len(&self) -> usize {
+        self.len()
+    }
+
+    fn find_in<'a>(&self, haystack: &impl MagicStringTrait<'a>, start: usize) -> Option<usize> {
+        find_needle(haystack, self, start)
+    }
+
+    fn rfind_in<'a>(&self, haystack: &impl MagicStringTrait<'a>, end: usize) -> Option<usize> {
+        rfind_needle(haystack, self, end)
+    }
+}
@@ -0,0 +1,81 @@
+use crate::MagicStringTrait;
+
+/// An iterator over the string cut into pieces of a fixed number of bytes,
+/// see [`MagicStringTrait::rechunk`]
+#[derive(Clone, Debug)]
+pub struct ReChunk<T> {
+    rest: T,
+    size: usize,
+}
+
+impl<T> ReChunk<T> {
+    pub(crate) fn new(string: T, size: usize) -> Self {
+        Self { rest: string, size }
+    }
+}
+
+impl<'a, T: MagicStringTrait<'a>> Iterator for ReChunk<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        // Chunks end early rather than in the middle of a char
+        let mut end = self.size.min(self.rest.len());
+        while !self.rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == 0 {
+            // A char larger than the chunk size gets a chunk of its own
+            end = self.rest.first_char()?.len_utf8();
+        }
+
+        let (chunk, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(chunk)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{MagicString, MagicStringTrait};
+
+    #[test]
+    fn rechunk() {
+        let s = ["ab", "é", "", "c🍅d", "ef"];
+        let string = MagicString::new(&s);
+        let chunks = string.rechunk(3).map(|c| c.to_string()).collect::<Vec<_>>();
+        // "é" and "🍅" don't fit in what is left of a chunk, so those chunks end early
+        assert_eq!(chunks, vec!["ab", "éc", "🍅", "def"]);
+
+        let chunks = string.rechunk(4).map(|c| c.to_string()).collect::<Vec<_>>();
+        assert_eq!(chunks, vec!["abé", "c", "🍅", "def"]);
+        assert!(string.rechunk(100).eq([string]));
+
+        // The chunks are views that can span slices
+        let mut chunks = string.rechunk(3);
+        let chunk = chunks.nth(1).unwrap();
+        assert_eq!(chunk.num_slices(), 2);
+        assert_eq!(chunk.to_string(), "éc");
+    }
+
+    #[test]
+    fn rechunk_small_sizes() {
+        let s = ["a🍅", "é"];
+        let string = MagicString::new(&s);
+        let chunks = string.rechunk(1).map(|c| c.to_string()).collect::<Vec<_>>();
+        assert_eq!(chunks, vec!["a", "🍅", "é"]);
+        let chunks = string.get(1..).rechunk(2).map(|c| c.to_string()).collect::<Vec<_>>();
+        assert_eq!(chunks, vec!["🍅", "é"]);
+        assert_eq!(MagicString::default().rechunk(3).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn rechunk_zero() {
+        let s = ["ab"];
+        let _ = MagicString::new(&s).rechunk(0);
+    }
+}
@@ -0,0 +1,322 @@
+use core::fmt;
+use core::iter::Chain;
+use core::ops::Add;
+
+use crate::MagicStringTrait;
+
+/// A string repeated a number of times, see [`MagicStringTrait::repeat`]
+///
+/// After splitting or trimming the first and last copy may only be partially
+/// there, so those are kept next to the number of whole copies in between.
+#[derive(Clone, Copy)]
+pub struct Repeat<T> {
+    head: T,
+    unit: T,
+    count: usize,
+    tail: T,
+}
+
+impl<'a, T: MagicStringTrait<'a>> Repeat<T> {
+    /// Repeat `unit` `count` times.
+    pub fn new(unit: T, count: usize) -> Self {
+        let (empty, _) = unit.split_at(0);
+        Self { head: empty, unit, count, tail: empty }
+    }
+}
+
+/// Iterator over `count` copies of what `make` produces for a unit
+#[derive(Clone, Debug)]
+pub struct Cycles<T, I> {
+    unit: T,
+    make: fn(&T) -> I,
+    count: usize,
+    front: Option<I>,
+    back: Option<I>,
+}
+
+impl<T, I> Cycles<T, I> {
+    fn new(unit: T, count: usize, make: fn(&T) -> I) -> Self {
+        Self { unit, make, count, front: None, back: None }
+    }
+}
+
+impl<T, I: Iterator> Iterator for Cycles<T, I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.front.as_mut().and_then(Iterator::next) {
+                return Some(item);
+            }
+            // The last copy may already have been started from the back
+            if self.count == 0 {
+                return self.back.as_mut()?.next();
+            }
+            self.count -= 1;
+            self.front = Some((self.make)(&self.unit));
+        }
+    }
+}
+
+impl<T, I: DoubleEndedIterator> DoubleEndedIterator for Cycles<T, I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.back.as_mut().and_then(DoubleEndedIterator::next_back) {
+                return Some(item);
+            }
+            if self.count == 0 {
+                return self.front.as_mut()?.next_back();
+            }
+            self.count -= 1;
+            self.back = Some((self.make)(&self.unit));
+        }
+    }
+}
+
+/// Char indices of a [`Repeat`], counted over all copies
+#[derive(Clone, Debug)]
+pub struct RepeatCharIndices<C> {
+    chars: C,
+    // The chars that haven't been yielded yet are in `index..end`
+    index: usize,
+    end: usize,
+}
+
+impl<C: Iterator<Item = char>> Iterator for RepeatCharIndices<C> {
+    type Item = (usize, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.chars.next()?;
+        let index = self.index;
+        self.index += c.len_utf8();
+        Some((index, c))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chars.size_hint()
+    }
+}
+
+impl<C: DoubleEndedIterator<Item = char>> DoubleEndedIterator for RepeatCharIndices<C> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let c = self.chars.next_back()?;
+        self.end -= c.len_utf8();
+        Some((self.end, c))
+    }
+}
+
+type Repeated<T, I> = Chain<Chain<I, Cycles<T, I>>, I>;
+
+impl<'a, T: MagicStringTrait<'a>> MagicStringTrait<'a> for Repeat<T> {
+    type Iter = Repeated<T, T::Iter>;
+    type Bytes = Repeated<T, T::Bytes>;
+    type Chars = Repeated<T, T::Chars>;
+    type CharIndices = RepeatCharIndices<Self::Chars>;
+
+    fn len(&self) -> usize {
+        self.head.len() + self.count * self.unit.len() + self.tail.len()
+    }
+
+    fn iter(&self) -> Self::Iter {
+        let units = Cycles::new(self.unit, self.count, T::iter);
+        self.head.iter().chain(units).chain(self.tail.iter())
+    }
+
+    fn bytes(&self) -> Self::Bytes {
+        let units = Cycles::new(self.unit, self.count, T::bytes);
+        self.head.bytes().chain(units).chain(self.tail.bytes())
+    }
+
+    fn chars(&self) -> Self::Chars {
+        let units = Cycles::new(self.unit, self.count, T::chars);
+        self.head.chars().chain(units).chain(self.tail.chars())
+    }
+
+    fn char_indices(&self) -> Self::CharIndices {
+        RepeatCharIndices { chars: self.chars(), index: 0, end: self.len() }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.head.is_empty() && self.tail.is_empty() && (self.count == 0 || self.unit.is_empty())
+    }
+
+    // The copy the index falls in is split, ending the left half and starting
+    // the right half.
+    fn split_at(&self, index: usize) -> (Self, Self) {
+        let (empty, _) = self.unit.split_at(0);
+        let head_len = self.head.len();
+        let units_len = self.count * self.unit.len();
+
+        if index <= head_len {
+            let (left, right) = self.head.split_at(index);
+            let left = Self { head: left, count: 0, tail: empty, ..*self };
+            (left, Self { head: right, ..*self })
+        } else if index < head_len + units_len {
+            let index = index - head_len;
+            let (copies, index) = (index / self.unit.len(), index % self.unit.len());
+            let (left, right) = self.unit.split_at(index);
+            let count = self.count - copies - 1;
+            let left = Self { count: copies, tail: left, ..*self };
+            (left, Self { head: right, count, ..*self })
+        } else {
+            let (left, right) = self.tail.split_at(index - head_len - units_len);
+            let right = Self { head: empty, count: 0, tail: right, ..*self };
+            (Self { tail: left, ..*self }, right)
+        }
+    }
+
+    fn trim_start_matches_fn(&self, mut pred: impl FnMut(char) -> bool) -> Self {
+        let head = self.head.trim_start_matches_fn(&mut pred);
+        if !head.is_empty() {
+            return Self { head, ..*self };
+        }
+
+        if self.count > 0 {
+            let unit = self.unit.trim_start_matches_fn(&mut pred);
+            if !unit.is_empty() {
+                return Self { head: unit, count: self.count - 1, ..*self };
+            }
+        }
+
+        let tail = self.tail.trim_start_matches_fn(&mut pred);
+        Self { head, count: 0, tail, ..*self }
+    }
+
+    fn trim_end_matches_fn(&self, mut pred: impl FnMut(char) -> bool) -> Self {
+        let tail = self.tail.trim_end_matches_fn(&mut pred);
+        if !tail.is_empty() {
+            return Self { tail, ..*self };
+        }
+
+        if self.count > 0 {
+            let unit = self.unit.trim_end_matches_fn(&mut pred);
+            if !unit.is_empty() {
+                return Self { count: self.count - 1, tail: unit, ..*self };
+            }
+        }
+
+        let head = self.head.trim_end_matches_fn(&mut pred);
+        Self { head, count: 0, tail, ..*self }
+    }
+
+    fn pop(&mut self) -> Option<char> {
+        if let Some(c) = self.tail.pop() {
+            return Some(c);
+        }
+
+        if self.count > 0 && !self.unit.is_empty() {
+            self.count -= 1;
+            self.tail = self.unit;
+            return self.tail.pop();
+        }
+
+        self.head.pop()
+    }
+}
+
+impl<'a, T> fmt::Display for Repeat<T>
+where
+    Self: MagicStringTrait<'a>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for slice in self.iter() {
+            write!(f, "{slice}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T> fmt::Debug for Repeat<T>
+where
+    Self: MagicStringTrait<'a>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for slice in self.iter() {
+            write!(f, "{slice:?}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T, Rhs> Add<Rhs> for Repeat<T>
+where
+    Self: MagicStringTrait<'a>,
+    Rhs: MagicStringTrait<'a>,
+{
+    type Output = crate::Concat<Self, Rhs>;
+
+    fn add(self, rhs: Rhs) -> Self::Output {
+        self.concat(rhs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{MagicString, MagicStringTrait};
+
+    #[test]
+    fn repeat() {
+        let s = ["ab", "", "🍅"];
+        let string = MagicString::new(&s);
+
+        let none = string.repeat(0);
+        assert_eq!(none.len(), 0);
+        assert!(none.is_empty());
+        assert_eq!(none.to_string(), "".to_string());
+
+        let once = string.repeat(1);
+        assert_eq!(once.len(), string.len());
+        assert_eq!(once.to_string(), string.to_string());
+
+        let thrice = string.repeat(3);
+        assert_eq!(thrice.len(), 18);
+        assert_eq!(thrice.to_string(), "ab🍅ab🍅ab🍅".to_string());
+        assert_eq!(thrice.iter().count(), 9);
+    }
+
+    #[test]
+    fn iterators() {
+        let s = ["a", "é"];
+        let string = MagicString::new(&s).repeat(3);
+        let expected = "aéaéaé";
+
+        assert!(string.iter().rev().eq(["é", "a", "é", "a", "é", "a"]));
+        assert!(string.bytes().eq(expected.bytes()));
+        assert!(string.chars().rev().eq(expected.chars().rev()));
+        assert!(string.char_indices().eq(expected.char_indices()));
+        assert!(string.char_indices().rev().eq(expected.char_indices().rev()));
+
+        // Meeting in the middle doesn't repeat or skip anything
+        let mut iter = string.iter();
+        assert_eq!(iter.next(), Some("a"));
+        assert_eq!(iter.next_back(), Some("é"));
+        assert_eq!(iter.collect::<Vec<_>>(), vec!["é", "a", "é", "a"]);
+    }
+
+    #[test]
+    fn split_trim_pop() {
+        let s = ["abc"];
+        let string = MagicString::new(&s).repeat(3);
+        let expected = "abcabcabc";
+
+        for index in 0..=expected.len() {
+            let (left, right) = string.split_at(index);
+            assert_eq!(left.to_string(), expected[..index].to_string());
+            assert_eq!(right.to_string(), expected[index..].to_string());
+            assert_eq!(left.len() + right.len(), expected.len());
+
+            let (_, inner) = right.split_at(1.min(right.len()));
+            let trimmed = inner.trim_matches_fn(|c| c != 'b');
+            let inner = inner.to_string();
+            assert_eq!(trimmed.to_string(), inner.trim_matches(|c| c != 'b').to_string());
+        }
+
+        let s = ["ab", "", "c"];
+        let mut popped = MagicString::new(&s).repeat(3);
+        let mut actual = Vec::new();
+        while let Some(c) = popped.pop() {
+            actual.push(c);
+        }
+        assert!(actual.into_iter().eq(expected.chars().rev()));
+    }
+}
@@ -0,0 +1,173 @@
+use alloc::string::String;
+
+use crate::find::find_needle;
+use crate::{MagicStringTrait, Pattern};
+
+/// Replaces either a [`char`] or a `&str` with another, writing the result
+/// into a [`String`] owned by the caller.
+pub trait Replace<P> {
+    /// Append the string to `out`, with every match of `from` replaced by `to`.
+    fn replace_into(&self, from: P, to: P, out: &mut String);
+
+    /// Append the string to `out`, with the first `count` matches of `from` replaced by `to`.
+    /// ```
+    /// use magicstring::{MagicString, Replace};
+    /// let input = ["a-b", "-c-", "d"];
+    /// let string = MagicString::new(&input);
+    /// let mut out = String::new();
+    /// string.replacen_into("-", "+", 2, &mut out);
+    /// assert_eq!(out, "a+b+c-d");
+    /// ```
+    fn replacen_into<'a>(&self, from: P, to: P, count: usize, out: &mut String)
+    where
+        Self: MagicStringTrait<'a>,
+        P: Pattern,
+    {
+        // Find where the `count`th match ends, searching on from the end of each match
+        let mut end = None;
+        let mut rest = *self;
+        let mut offset = 0;
+        for _ in 0..count {
+            let Some(start) = from.find_in(&rest, 0) else {
+                break;
+            };
+            let match_end = start + from.match_len();
+            end = Some(offset + match_end);
+            // The next empty match is after the next char, not at the same place
+            let step = match from.match_len() {
+                0 => match rest.split_at(match_end).1.first_char() {
+                    Some(c) => match_end + c.len_utf8(),
+                    None => break,
+                },
+                _ => match_end,
+            };
+            (_, rest) = rest.split_at(step);
+            offset += step;
+        }
+
+        // Up to there every match is replaced, after it nothing is
+        let Some(end) = end else {
+            self.iter().for_each(|s| out.push_str(s));
+            return;
+        };
+        let (head, tail) = self.split_at(end);
+        head.replace_into(from, to, out);
+        tail.iter().for_each(|s| out.push_str(s));
+    }
+}
+
+impl<'a, T: MagicStringTrait<'a>> Replace<char> for T {
+    fn replace_into(&self, from: char, to: char, out: &mut String) {
+        for c in self.chars() {
+            out.push(if c == from { to } else { c });
+        }
+    }
+}
+
+impl<'a, 'b, T: MagicStringTrait<'a>> Replace<&'b str> for T {
+    fn replace_into(&self, from: &'b str, to: &'b str, out: &mut String) {
+        // Like `str::replace`, an empty pattern matches between every char
+        if from.is_empty() {
+            out.push_str(to);
+            for c in self.chars() {
+                out.push(c);
+                out.push_str(to);
+            }
+            return;
+        }
+
+        // Each search starts after the last match, not at the start of the string
+        let mut rest = *self;
+        while let Some(start) = find_needle(&rest, &from, 0) {
+            let (before, after) = rest.split_at(start);
+            before.iter().for_each(|s| out.push_str(s));
+            out.push_str(to);
+            (_, rest) = after.split_at(from.len());
+        }
+        rest.iter().for_each(|s| out.push_str(s));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MagicString;
+
+    #[test]
+    fn replace_char() {
+        let s = ["a-b", "-", "c"];
+        let string = MagicString::new(&s);
+        let mut out = String::from(">");
+        string.replace_into('-', '🍅', &mut out);
+        assert_eq!(out, ">a🍅b🍅c".to_string());
+    }
+
+    #[test]
+    fn replace_str() {
+        let inputs: [&[&str]; 5] = [
+            &["ab", "c", "abc", "d"],
+            &["a", "a", "a", "aa"],
+            &["x🍅", "🍅y", "", "🍅"],
+            &["no match"],
+            &[],
+        ];
+        let patterns = ["abc", "aa", "🍅🍅", "bc", "a", ""];
+
+        for input in inputs {
+            let string = MagicString::new(input);
+            let expected_input = input.concat();
+            for pat in patterns {
+                let mut actual = String::new();
+                string.replace_into(pat, "<>", &mut actual);
+                let expected = expected_input.replace(pat, "<>");
+                assert_eq!(expected, actual, "replacing {pat:?} in {input:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn replacen() {
+        let inputs: [&[&str]; 4] = [
+            &["ab", "c", "abc", "d", "a", "bc"],
+            &["a", "a", "a", "aa"],
+            &["x🍅", "🍅y", "", "🍅"],
+            &[],
+        ];
+        let patterns = ["abc", "aa", "🍅", "a", ""];
+
+        for input in inputs {
+            let string = MagicString::new(input);
+            let expected_input = input.concat();
+            // Fewer, as many and more replacements than there are matches
+            for count in 0..8 {
+                for pat in patterns {
+                    let mut actual = String::new();
+                    string.replacen_into(pat, "<>", count, &mut actual);
+                    let expected = expected_input.replacen(pat, "<>", count);
+                    assert_eq!(expected, actual, "replacing {count} {pat:?} in {input:?}");
+                }
+
+                let mut actual = String::new();
+                string.replacen_into('a', '_', count, &mut actual);
+                assert_eq!(expected_input.replacen('a', "_", count), actual);
+            }
+        }
+    }
+
+    // Implementing only `replace_into` is enough, as it was before `replacen_into`
+    struct Bytes<'a>(&'a [u8]);
+
+    impl Replace<u8> for Bytes<'_> {
+        fn replace_into(&self, from: u8, to: u8, out: &mut String) {
+            let replace = |&b: &u8| char::from(if b == from { to } else { b });
+            out.extend(self.0.iter().map(replace));
+        }
+    }
+
+    #[test]
+    fn replace_into_is_enough() {
+        let mut out = String::new();
+        Bytes(b"a-b-").replace_into(b'-', b'+', &mut out);
+        assert_eq!(out, "a+b+");
+    }
+}
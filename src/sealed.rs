@@ -3,4 +3,5 @@ use super::MagicString;
 pub trait Sealed {}
 
 impl<'a> Sealed for MagicString<'a> {}
-
+impl Sealed for char {}
+impl Sealed for &str {}
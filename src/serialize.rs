@@ -0,0 +1,64 @@
+use serde::{Serialize, Serializer};
+
+use crate::{Concat, MagicString, MagicStringTrait};
+
+// Strings made up of a single slice are serialized directly, the others are
+// streamed through their `Display` impl so nothing has to be collected first
+fn serialize<'a, S: Serializer>(
+    string: &(impl MagicStringTrait<'a> + core::fmt::Display),
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match string.as_str() {
+        Some(s) => serializer.serialize_str(s),
+        None => serializer.collect_str(string),
+    }
+}
+
+impl<'a> Serialize for MagicString<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self, serializer)
+    }
+}
+
+impl<'a, L, R> Serialize for Concat<L, R>
+where
+    L: MagicStringTrait<'a>,
+    R: MagicStringTrait<'a>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self, serializer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{MagicString, MagicStringTrait};
+
+    #[test]
+    fn serialize() {
+        let inputs: [&[&str]; 4] = [
+            &["ab", "c"],
+            &["", "single", ""],
+            &["\"quoted\"", " and\n", "🍅"],
+            &[],
+        ];
+        for input in inputs {
+            let string = MagicString::new(input);
+            let expected = serde_json::to_string(&input.concat()).unwrap();
+            assert_eq!(serde_json::to_string(&string).unwrap(), expected);
+
+            let concat = string.concat("!");
+            let expected = serde_json::to_string(&(input.concat() + "!")).unwrap();
+            assert_eq!(serde_json::to_string(&concat).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let s = ["he said ", "\"hi\"", "\t"];
+        let string = MagicString::new(&s).get(3..);
+        let json = serde_json::to_string(&string).unwrap();
+        let back: String = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, "said \"hi\"\t");
+    }
+}
@@ -0,0 +1,234 @@
+use crate::matches::{MatchIndices, Matches};
+use crate::pattern::Pattern;
+use crate::{Find, MagicStringTrait};
+
+/// Splits a string into the pieces between the matches of either a [`char`] or a `&str`.
+/// The pieces are views into the string, so nothing is copied.
+pub trait Split<P>: Sized {
+    /// Like [`str::split`], the pieces between the separators.
+    /// ```
+    /// use magicstring::{MagicString, MagicStringTrait, Split};
+    /// let input = ["a,b", ",", "c,"];
+    /// let string = MagicString::new(&input);
+    /// let pieces = string.split(',').map(|p| p.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(pieces, vec!["a", "b", "c", ""]);
+    /// ```
+    fn split(&self, pat: P) -> SplitIter<Self, P>;
+
+    /// Like [`str::split_terminator`], the same as [`Split::split`] except that
+    /// there is no trailing empty piece when the string ends with a separator.
+    /// ```
+    /// use magicstring::{MagicString, MagicStringTrait, Split};
+    /// let input = ["a;b", ";"];
+    /// let string = MagicString::new(&input);
+    /// let records = string.split_terminator(';').map(|p| p.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(records, vec!["a", "b"]);
+    /// ```
+    fn split_terminator(&self, pat: P) -> SplitIter<Self, P>;
+
+    /// Like [`str::split_inclusive`], every piece keeps the separator that ends it.
+    /// There is no trailing empty piece when the string ends with a separator.
+    /// ```
+    /// use magicstring::{MagicString, MagicStringTrait, Split};
+    /// let input = ["one\ntw", "o\n", "three"];
+    /// let string = MagicString::new(&input);
+    /// let lines = string.split_inclusive('\n').map(|l| l.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(lines, vec!["one\n", "two\n", "three"]);
+    /// ```
+    fn split_inclusive(&self, pat: P) -> SplitIter<Self, P>;
+}
+
+impl<'a, T: MagicStringTrait<'a>, P: Pattern> Split<P> for T {
+    fn split(&self, pat: P) -> SplitIter<Self, P> {
+        SplitIter::new(*self, pat, false, false)
+    }
+
+    fn split_terminator(&self, pat: P) -> SplitIter<Self, P> {
+        SplitIter::new(*self, pat, false, true)
+    }
+
+    fn split_inclusive(&self, pat: P) -> SplitIter<Self, P> {
+        SplitIter::new(*self, pat, true, true)
+    }
+}
+
+/// An iterator over the pieces of a string between the matches of a pattern,
+/// see [`Split`]
+#[derive(Clone, Debug)]
+pub struct SplitIter<T, P> {
+    matches: MatchIndices<T, P>,
+    // What is left of the string, starting at byte `consumed` of the original
+    rest: T,
+    consumed: usize,
+    // Pieces keep the separator that ends them
+    inclusive: bool,
+    // Separators end pieces instead of separating them, so there is no empty last piece
+    terminated: bool,
+    done: bool,
+}
+
+impl<'a, T: MagicStringTrait<'a>, P: Pattern> SplitIter<T, P> {
+    fn new(string: T, pat: P, inclusive: bool, terminated: bool) -> Self {
+        let matches = string.match_indices(pat);
+        Self { matches, rest: string, consumed: 0, inclusive, terminated, done: false }
+    }
+}
+
+impl<'a, T: MagicStringTrait<'a>, P: Pattern> Iterator for SplitIter<T, P> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let Some((start, separator)) = self.matches.next() else {
+            self.done = true;
+            // A separator at the very end already finished the last piece
+            if self.terminated && self.rest.is_empty() {
+                return None;
+            }
+            return Some(self.rest);
+        };
+
+        let start = start - self.consumed;
+        let end = start + separator.len();
+        let (piece, _) = match self.inclusive {
+            true => self.rest.split_at(end),
+            false => self.rest.split_at(start),
+        };
+
+        (_, self.rest) = self.rest.split_at(end);
+        self.consumed += end;
+        Some(piece)
+    }
+}
+
+/// An iterator over the pieces of a string between runs of ASCII whitespace,
+/// see [`MagicStringTrait::split_ascii_whitespace`]
+#[derive(Clone, Debug)]
+pub struct SplitAsciiWhitespace<T> {
+    rest: T,
+}
+
+impl<T> SplitAsciiWhitespace<T> {
+    pub(crate) fn new(string: T) -> Self {
+        Self { rest: string }
+    }
+}
+
+impl<'a, T: MagicStringTrait<'a>> Iterator for SplitAsciiWhitespace<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Skipping the whole run at once means runs spanning slices are one separator
+        let start = self.rest.find(|c: char| !c.is_ascii_whitespace())?;
+        (_, self.rest) = self.rest.split_at(start);
+
+        let end = self.rest.find(|c: char| c.is_ascii_whitespace()).unwrap_or(self.rest.len());
+        let (piece, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(piece)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MagicString;
+
+    #[test]
+    fn split() {
+        let inputs: [&[&str]; 6] = [
+            &["a,b", ",", "c"],
+            &["a,", ",b,"],
+            &[",", "", "a", ","],
+            &["🍅", ",🍅"],
+            &[""],
+            &[],
+        ];
+        for input in inputs {
+            let string = MagicString::new(input);
+            let expected = input.concat();
+            let actual = string.split(',').map(|p| p.to_string()).collect::<Vec<_>>();
+            assert_eq!(expected.split(',').collect::<Vec<_>>(), actual, "{input:?}");
+            let actual = string.split_terminator(',').map(|p| p.to_string()).collect::<Vec<_>>();
+            assert_eq!(expected.split_terminator(',').collect::<Vec<_>>(), actual, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn split_terminator() {
+        let s = ["a;b;", "c;"];
+        let string = MagicString::new(&s);
+        let actual = string.split_terminator(';').map(|p| p.to_string()).collect::<Vec<_>>();
+        // The terminator of "b" is at the end of a slice
+        assert_eq!(actual, vec!["a", "b", "c"]);
+
+        let s = ["a;b", ";c"];
+        let string = MagicString::new(&s);
+        let actual = string.split_terminator(';').map(|p| p.to_string()).collect::<Vec<_>>();
+        assert_eq!(actual, vec!["a", "b", "c"]);
+
+        let s = ["a;", ";"];
+        let string = MagicString::new(&s);
+        let actual = string.split_terminator(";;").map(|p| p.to_string()).collect::<Vec<_>>();
+        assert_eq!(actual, vec!["a"]);
+    }
+
+    #[test]
+    fn split_inclusive() {
+        let inputs: [&[&str]; 6] = [
+            &["a\nb", "\n", "c"],
+            &["a\n", "\nb\n"],
+            &["\n", "", "a", "\n"],
+            &["🍅", "\n🍅"],
+            &[""],
+            &[],
+        ];
+        for input in inputs {
+            let string = MagicString::new(input);
+            let expected = input.concat();
+            let actual = string.split_inclusive('\n').map(|p| p.to_string()).collect::<Vec<_>>();
+            let expected = expected.split_inclusive('\n').collect::<Vec<_>>();
+            assert_eq!(expected, actual, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn split_inclusive_str() {
+        let s = ["a, b,", " c, ", "d"];
+        let string = MagicString::new(&s);
+        let actual = string.split_inclusive(", ").map(|p| p.to_string()).collect::<Vec<_>>();
+        // The second separator is split over a slice boundary
+        assert_eq!(actual, vec!["a, ", "b, ", "c, ", "d"]);
+    }
+
+    #[test]
+    fn split_ascii_whitespace() {
+        let inputs: [&[&str]; 7] = [
+            &["a \t", "\tb", " c"],
+            &[" \n", "", "\r\n", "a\x0c"],
+            &["ab", "cd", "  ", "e"],
+            &["a\u{3000}b", " 🍅\t"],
+            &["  ", "\t"],
+            &[""],
+            &[],
+        ];
+        for input in inputs {
+            let string = MagicString::new(input);
+            let expected = input.concat();
+            let actual = string.split_ascii_whitespace().map(|p| p.to_string());
+            let expected = expected.split_ascii_whitespace();
+            assert!(actual.eq(expected), "{input:?}");
+        }
+
+        // Pieces are views, also when they are spread over several slices
+        let s = ["\tab", "c d", "e\t"];
+        let string = MagicString::new(&s);
+        let pieces = string.split_ascii_whitespace().collect::<Vec<_>>();
+        assert_eq!(pieces.len(), 2);
+        assert!(pieces[0].iter().eq(["ab", "c"]));
+        assert!(pieces[1].iter().eq(["d", "e"]));
+    }
+}
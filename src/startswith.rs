@@ -0,0 +1,104 @@
+use crate::{Concat, MagicString, MagicStringTrait, Repeat};
+
+/// Checks if a [`MagicString`] starts with either a [`char`], a `&str`, a char
+/// matching a predicate or another magic string.
+pub trait StartsWith<P> {
+    /// Does the string start with the pattern?
+    fn starts_with(&self, pat: P) -> bool;
+}
+
+// Compares the bytes of both strings, no matter how they are split into slices
+fn starts_with<'a, 'b>(
+    haystack: &impl MagicStringTrait<'a>,
+    needle: &impl MagicStringTrait<'b>,
+) -> bool {
+    // A needle that is too long can't match, so there is no need to look at the bytes
+    if needle.len() > haystack.len() {
+        return false;
+    }
+    let mut bytes = haystack.bytes();
+    needle.bytes().all(|b| bytes.next() == Some(b))
+}
+
+impl<'a, T: MagicStringTrait<'a>> StartsWith<char> for T {
+    fn starts_with(&self, pat: char) -> bool {
+        self.chars().next() == Some(pat)
+    }
+}
+
+impl<'a, T: MagicStringTrait<'a>, F: FnMut(char) -> bool> StartsWith<F> for T {
+    fn starts_with(&self, pat: F) -> bool {
+        self.chars().next().is_some_and(pat)
+    }
+}
+
+impl<'a, 'b, T: MagicStringTrait<'a>> StartsWith<&'b str> for T {
+    fn starts_with(&self, pat: &'b str) -> bool {
+        starts_with(self, &pat)
+    }
+}
+
+impl<'a, 'b, T: MagicStringTrait<'a>> StartsWith<MagicString<'b>> for T {
+    fn starts_with(&self, pat: MagicString<'b>) -> bool {
+        starts_with(self, &pat)
+    }
+}
+
+impl<'a, 'b, T, L, R> StartsWith<Concat<L, R>> for T
+where
+    T: MagicStringTrait<'a>,
+    Concat<L, R>: MagicStringTrait<'b>,
+{
+    fn starts_with(&self, pat: Concat<L, R>) -> bool {
+        starts_with(self, &pat)
+    }
+}
+
+impl<'a, 'b, T, U> StartsWith<Repeat<U>> for T
+where
+    T: MagicStringTrait<'a>,
+    Repeat<U>: MagicStringTrait<'b>,
+{
+    fn starts_with(&self, pat: Repeat<U>) -> bool {
+        starts_with(self, &pat)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn starts_with_char() {
+        let s = ["", "🍅b"];
+        let string = MagicString::new(&s);
+        assert!(string.starts_with('🍅'));
+        assert!(!string.starts_with('b'));
+        assert!(string.starts_with(|c: char| !c.is_ascii()));
+        assert!(!MagicString::default().starts_with(|_| true));
+    }
+
+    #[test]
+    fn starts_with_str() {
+        let s = ["a", "bc", "d"];
+        let string = MagicString::new(&s);
+        assert!(string.starts_with("abc"));
+        assert!(string.starts_with("abcd"));
+        assert!(string.starts_with(""));
+        assert!(!string.starts_with("abcde"));
+        assert!(!string.starts_with("b"));
+    }
+
+    #[test]
+    fn starts_with_magic_string() {
+        let s = ["ab", "cd"];
+        let string = MagicString::new(&s);
+        let prefix = ["a", "", "bc"];
+        let prefix = MagicString::new(&prefix);
+        assert!(string.starts_with(prefix));
+        assert!(!prefix.starts_with(string));
+        assert!(string.starts_with(prefix.concat("d")));
+        assert!(!string.starts_with(prefix.concat("x")));
+        assert!(string.starts_with(MagicString::new(&["a"]).repeat(1)));
+    }
+}
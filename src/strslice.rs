@@ -0,0 +1,85 @@
+use core::option::IntoIter;
+use core::str::Bytes as StdBytes;
+use core::str::CharIndices as StdCharIndices;
+use core::str::Chars as StdChars;
+
+use crate::MagicStringTrait;
+
+// -----------------------------------------------------------------------------
+//     - String slice -
+//     A plain `&str` is a magic string made up of a single slice,
+//     or of no slices at all when it's empty.
+//     Everything is forwarded to `str` with fully qualified calls, as the trait
+//     methods would otherwise shadow the inherent ones.
+// -----------------------------------------------------------------------------
+impl<'a> MagicStringTrait<'a> for &'a str {
+    type Iter = IntoIter<&'a str>;
+    type Bytes = StdBytes<'a>;
+    type Chars = StdChars<'a>;
+    type CharIndices = StdCharIndices<'a>;
+
+    fn len(&self) -> usize {
+        str::len(self)
+    }
+
+    fn iter(&self) -> Self::Iter {
+        Some(*self).filter(|s| !str::is_empty(s)).into_iter()
+    }
+
+    fn bytes(&self) -> Self::Bytes {
+        str::bytes(self)
+    }
+
+    fn chars(&self) -> Self::Chars {
+        str::chars(self)
+    }
+
+    fn char_indices(&self) -> Self::CharIndices {
+        str::char_indices(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        str::is_empty(self)
+    }
+
+    fn split_at(&self, index: usize) -> (Self, Self) {
+        str::split_at(self, index)
+    }
+
+    fn trim_start_matches_fn(&self, pred: impl FnMut(char) -> bool) -> Self {
+        str::trim_start_matches(self, pred)
+    }
+
+    fn trim_end_matches_fn(&self, pred: impl FnMut(char) -> bool) -> Self {
+        str::trim_end_matches(self, pred)
+    }
+
+    fn pop(&mut self) -> Option<char> {
+        let c = str::chars(self).next_back()?;
+        *self = &self[..str::len(self) - c.len_utf8()];
+        Some(c)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::MagicStringTrait;
+
+    #[test]
+    fn split_and_trim() {
+        let s = "  ab cd ";
+        let (left, right) = MagicStringTrait::split_at(&s, 4);
+        assert_eq!(left, "  ab");
+        assert_eq!(right, " cd ");
+        assert_eq!(MagicStringTrait::trim(&s), "ab cd");
+    }
+
+    #[test]
+    fn pop() {
+        let mut s = "a🍅";
+        assert_eq!(MagicStringTrait::pop(&mut s), Some('🍅'));
+        assert_eq!(s, "a");
+        assert_eq!(MagicStringTrait::pop(&mut s), Some('a'));
+        assert_eq!(MagicStringTrait::pop(&mut s), None);
+    }
+}
@@ -0,0 +1,112 @@
+use core::cell::Cell;
+use core::iter::FlatMap;
+use core::str::Bytes as StdBytes;
+use core::str::Chars as StdChars;
+
+use crate::{CharIndices, MagicIter, MagicString, MagicStringTrait};
+
+// How much of a `Tracked` string has been looked at
+#[derive(Default)]
+pub(crate) struct Counts {
+    // Calls to `len`
+    pub(crate) lens: Cell<usize>,
+    // Slices stepped over by `iter`, `bytes` and `chars`
+    pub(crate) steps: Cell<usize>,
+}
+
+// A `MagicString` that counts how often its length is asked for and how many of its
+// slices are walked, for tests that check how much work something does
+#[derive(Clone, Copy)]
+pub(crate) struct Tracked<'a> {
+    string: MagicString<'a>,
+    counts: &'a Counts,
+    // Reported instead of the real length
+    len: Option<usize>,
+}
+
+impl<'a> Tracked<'a> {
+    pub(crate) fn new(string: MagicString<'a>, counts: &'a Counts) -> Self {
+        Self { string, counts, len: None }
+    }
+
+    // Claim a length the string doesn't have, like one too large to allocate
+    #[cfg(feature = "alloc")]
+    pub(crate) fn with_len(self, len: usize) -> Self {
+        Self { len: Some(len), ..self }
+    }
+
+    fn wrap(&self, string: MagicString<'a>) -> Self {
+        Self::new(string, self.counts)
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Steps<'a> {
+    inner: MagicIter<'a>,
+    counts: &'a Counts,
+}
+
+impl<'a> Iterator for Steps<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.counts.steps.set(self.counts.steps.get() + 1);
+        self.inner.next()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Steps<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.counts.steps.set(self.counts.steps.get() + 1);
+        self.inner.next_back()
+    }
+}
+
+impl<'a> MagicStringTrait<'a> for Tracked<'a> {
+    type Iter = Steps<'a>;
+    type Bytes = FlatMap<Steps<'a>, StdBytes<'a>, fn(&'a str) -> StdBytes<'a>>;
+    type Chars = FlatMap<Steps<'a>, StdChars<'a>, fn(&'a str) -> StdChars<'a>>;
+    type CharIndices = CharIndices<'a>;
+
+    fn len(&self) -> usize {
+        self.counts.lens.set(self.counts.lens.get() + 1);
+        self.len.unwrap_or_else(|| self.string.len())
+    }
+
+    fn iter(&self) -> Self::Iter {
+        Steps { inner: self.string.iter(), counts: self.counts }
+    }
+
+    fn bytes(&self) -> Self::Bytes {
+        self.iter().flat_map(str::bytes)
+    }
+
+    fn chars(&self) -> Self::Chars {
+        self.iter().flat_map(str::chars)
+    }
+
+    fn char_indices(&self) -> Self::CharIndices {
+        self.string.char_indices()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len.map_or_else(|| self.string.is_empty(), |len| len == 0)
+    }
+
+    fn split_at(&self, index: usize) -> (Self, Self) {
+        let (left, right) = self.string.split_at(index);
+        (self.wrap(left), self.wrap(right))
+    }
+
+    fn trim_start_matches_fn(&self, pred: impl FnMut(char) -> bool) -> Self {
+        self.wrap(self.string.trim_start_matches_fn(pred))
+    }
+
+    fn trim_end_matches_fn(&self, pred: impl FnMut(char) -> bool) -> Self {
+        self.wrap(self.string.trim_end_matches_fn(pred))
+    }
+
+    fn pop(&mut self) -> Option<char> {
+        self.string.pop()
+    }
+}
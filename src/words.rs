@@ -0,0 +1,77 @@
+use alloc::string::String;
+use alloc::vec::{IntoIter, Vec};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::MagicStringTrait;
+
+/// An iterator over the words of a string, see [`MagicStringTrait::unicode_words`]
+#[derive(Clone, Debug)]
+pub struct UnicodeWords<T> {
+    string: T,
+    words: IntoIter<(usize, usize)>,
+}
+
+impl<'a, T: MagicStringTrait<'a>> UnicodeWords<T> {
+    pub(crate) fn new(string: T) -> Self {
+        // The word boundary rules need the text on both sides of a boundary,
+        // which isn't available across slices, so they're run on a copy
+        let mut text = String::with_capacity(string.len());
+        string.iter().for_each(|slice| text.push_str(slice));
+        let words = text
+            .unicode_word_indices()
+            .map(|(start, word)| (start, start + word.len()))
+            .collect::<Vec<_>>();
+
+        Self { string, words: words.into_iter() }
+    }
+}
+
+impl<'a, T: MagicStringTrait<'a>> Iterator for UnicodeWords<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, end) = self.words.next()?;
+        Some(self.string.get(start..end))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.words.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    use crate::{MagicString, MagicStringTrait};
+
+    #[test]
+    fn unicode_words() {
+        let inputs: [&[&str]; 5] = [
+            &["Hello, wor", "ld! It's", " 3.5 ", "o'", "clock."],
+            &["The quick (\"brown\")", " fox can't", " jump 32.3 feet, right?"],
+            &["日本", "語の", "テキスト", "です。"],
+            &["hy", "", "phen-", "ated", " words"],
+            &["", "..."],
+        ];
+        for input in inputs {
+            let string = MagicString::new(input);
+            let expected = input.concat();
+            let actual = string.unicode_words().map(|w| w.to_string()).collect::<Vec<_>>();
+            let expected = expected.unicode_words().collect::<Vec<_>>();
+            assert_eq!(expected, actual, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn words_are_views() {
+        let s = ["ab", "c d", "ef"];
+        let string = MagicString::new(&s);
+        let words = string.unicode_words().collect::<Vec<_>>();
+        assert_eq!(words.len(), 2);
+        // "abc" is made up of two slices, "def" was split at the space
+        assert!(words[0].iter().eq(["ab", "c"]));
+        assert!(words[1].iter().eq(["d", "ef"]));
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{Find, MagicStringTrait};
+
+/// An iterator over the lines of a string wrapped to a display width,
+/// see [`MagicStringTrait::wrap`]
+#[derive(Clone, Debug)]
+pub struct Wrap<T> {
+    rest: T,
+    width: usize,
+}
+
+impl<T> Wrap<T> {
+    pub(crate) fn new(string: T, width: usize) -> Self {
+        Self { rest: string, width }
+    }
+}
+
+impl<'a, T: MagicStringTrait<'a>> Iterator for Wrap<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let (mut line, _) = self.rest.truncate_to_width(self.width);
+        if line.is_empty() {
+            // A char wider than the whole line gets a line of its own
+            let c = self.rest.chars().next()?;
+            (line, _) = self.rest.split_at(c.len_utf8());
+        }
+
+        (_, self.rest) = self.rest.split_at(line.len());
+        Some(line)
+    }
+}
+
+/// An iterator over the lines of a string wrapped to a display width at whitespace,
+/// see [`MagicStringTrait::wrap_words`]
+#[derive(Clone, Debug)]
+pub struct WrapWords<T> {
+    rest: T,
+    width: usize,
+}
+
+impl<T> WrapWords<T> {
+    pub(crate) fn new(string: T, width: usize) -> Self {
+        Self { rest: string, width }
+    }
+}
+
+impl<'a, T: MagicStringTrait<'a>> WrapWords<T> {
+    // The line without its trailing whitespace
+    fn trim_end(line: T) -> T {
+        let end = line
+            .rfind(|c: char| !c.is_whitespace())
+            .map_or(0, |i| i + line.char_at(i).map_or(0, char::len_utf8));
+        line.split_at(end).0
+    }
+}
+
+impl<'a, T: MagicStringTrait<'a>> Iterator for WrapWords<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Lines never start with whitespace
+        let start = self.rest.find(|c: char| !c.is_whitespace())?;
+        (_, self.rest) = self.rest.split_at(start);
+
+        let (mut line, truncated) = self.rest.truncate_to_width(self.width);
+        let ends_at_space = self.rest.char_at(line.len()).is_some_and(char::is_whitespace);
+        if truncated && !ends_at_space {
+            match line.rfind(char::is_whitespace) {
+                Some(end) => (line, _) = line.split_at(end),
+                // A word longer than the line is broken like `wrap` does
+                None if line.is_empty() => {
+                    let c = self.rest.chars().next()?;
+                    (line, _) = self.rest.split_at(c.len_utf8());
+                }
+                None => {}
+            }
+        }
+
+        (_, self.rest) = self.rest.split_at(line.len());
+        Some(Self::trim_end(line))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use unicode_width::UnicodeWidthStr;
+
+    use crate::{MagicString, MagicStringTrait};
+
+    #[test]
+    fn wrap() {
+        let s = ["hello", " wor", "ld"];
+        let string = MagicString::new(&s);
+        let lines = string.wrap(4).map(|l| l.to_string()).collect::<Vec<_>>();
+        assert_eq!(lines, vec!["hell", "o wo", "rld"]);
+        assert_eq!(lines.concat(), s.concat());
+
+        let lines = string.wrap(100).map(|l| l.to_string()).collect::<Vec<_>>();
+        assert_eq!(lines, vec!["hello world"]);
+        assert_eq!(MagicString::default().wrap(4).count(), 0);
+    }
+
+    #[test]
+    fn wrap_long_slice() {
+        // Each line only measures itself, so a long slice doesn't take quadratic time
+        let text = "0123456789".repeat(100_000);
+        let s = [text.as_str()];
+        let mut lines = MagicString::new(&s).wrap(80);
+        assert_eq!(lines.clone().count(), 12_500);
+        assert!(lines.all(|l| l.len() == 80));
+    }
+
+    #[test]
+    fn wrap_wide_chars() {
+        let s = ["a日本", "語🍅b", "c"];
+        let string = MagicString::new(&s);
+        for width in 2..10 {
+            let lines = string.wrap(width).map(|l| l.to_string()).collect::<Vec<_>>();
+            assert_eq!(lines.concat(), s.concat());
+            assert!(lines.iter().all(|l| l.width() <= width), "{lines:?}");
+        }
+
+        let lines = string.wrap(3).map(|l| l.to_string()).collect::<Vec<_>>();
+        assert_eq!(lines, vec!["a日", "本", "語", "🍅b", "c"]);
+
+        // Chars wider than the line don't stop the wrapping
+        let lines = string.wrap(1).map(|l| l.to_string()).collect::<Vec<_>>();
+        assert_eq!(lines, vec!["a", "日", "本", "語", "🍅", "b", "c"]);
+    }
+
+    #[test]
+    fn wrap_words() {
+        let s = ["the quick br", "own fox  jumps", " over"];
+        let string = MagicString::new(&s);
+        let lines = string.wrap_words(10).map(|l| l.to_string()).collect::<Vec<_>>();
+        assert_eq!(lines, vec!["the quick", "brown fox", "jumps over"]);
+
+        // Whitespace right after a full line and around the lines is dropped
+        let lines = string.wrap_words(9).map(|l| l.to_string()).collect::<Vec<_>>();
+        assert_eq!(lines, vec!["the quick", "brown fox", "jumps", "over"]);
+
+        let s = ["  ", "a ", "", " b  "];
+        let string = MagicString::new(&s);
+        let lines = string.wrap_words(3).map(|l| l.to_string()).collect::<Vec<_>>();
+        assert_eq!(lines, vec!["a", "b"]);
+        let lines = string.wrap_words(4).map(|l| l.to_string()).collect::<Vec<_>>();
+        assert_eq!(lines, vec!["a  b"]);
+        assert_eq!(MagicString::new(&["  "]).wrap_words(3).count(), 0);
+    }
+
+    #[test]
+    fn wrap_words_long_slice() {
+        let text = "123456789 ".repeat(100_000);
+        let s = [text.as_str()];
+        let mut lines = MagicString::new(&s).wrap_words(80);
+        assert_eq!(lines.clone().count(), 12_500);
+        assert!(lines.all(|l| l.len() == 79));
+    }
+
+    #[test]
+    fn wrap_long_words() {
+        let s = ["a tremendous", "ly long word"];
+        let string = MagicString::new(&s);
+        let lines = string.wrap_words(6).map(|l| l.to_string()).collect::<Vec<_>>();
+        assert_eq!(lines, vec!["a", "tremen", "dously", "long", "word"]);
+
+        let s = ["日本語 ", "🍅"];
+        let lines = MagicString::new(&s).wrap_words(1).map(|l| l.to_string()).collect::<Vec<_>>();
+        assert_eq!(lines, vec!["日", "本", "語", "🍅"]);
+    }
+}
@@ -1,5 +1,5 @@
 #![no_std]
-use magicstring::MagicString;
+use magicstring::{Contains, Find, MagicString, MagicStringTrait};
 
 #[cfg(test)]
 mod test {
@@ -9,4 +9,18 @@ mod test {
     fn no_std_test() {
         let _ = MagicString::new([].as_slice());
     }
+
+    #[test]
+    fn no_std_api() {
+        let s = ["  ab", "c", "d  "];
+        let string = MagicString::new(&s).trim();
+        assert_eq!(string.len(), 4);
+        assert!(string.contains('c'));
+        assert_eq!(string.find('d'), Some(3));
+
+        let t = ["e"];
+        let t = MagicString::new(&t);
+        let concat = string.concat(t);
+        assert!(concat.chars().eq("abcde".chars()));
+    }
 }